@@ -0,0 +1,361 @@
+// Copyright ⓒ 2019 Florent Jugla
+//
+// Licensed under MIT license ([LICENSE-MIT](LICENSE-MIT) or http://opensource.org/licenses/MIT)
+
+// Conversions of huge numbers, in O(M(n) log n) instead of O(n²) : digits are split in two halves by
+// precomputed powers of the radix (radix^(len * 2^i), len digits fitting in a limb), both halves
+// being converted recursively. Multiplications use Karatsuba algorithm and divisions the recursive
+// algorithm of Burnikel and Ziegler, so that big numbers are never handled by schoolbook algorithms.
+//
+// Numbers are 32 bits limbs, least significant first, without null limbs at the end.
+
+use std::cmp::Ordering;
+
+use super::{
+    limbs_add, limbs_cmp, limbs_divmod_small, limbs_from_digits, limbs_mul, limbs_sub,
+    limbs_to_digits, limbs_trim, radix_power,
+};
+
+// number of limbs from which conversions are split in two halves
+pub(crate) const CONQUER_THRESHOLD: usize = 100;
+// number of limbs from which multiplications are split (Karatsuba)
+const KARATSUBA_THRESHOLD: usize = 48;
+// number of limbs from which divisions are split (Burnikel-Ziegler)
+const DIVISION_THRESHOLD: usize = 64;
+
+// number given by its digits in radix, most significant first
+pub(crate) fn from_digits(digits: &[u32], radix: u32) -> Vec<u32> {
+    let (power, len) = radix_power(radix);
+    let mut powers = vec![vec![power]];
+    while len << powers.len() <= digits.len() / 2 {
+        let last = powers.last().unwrap();
+        powers.push(mul(last, last));
+    }
+    from_digits_rec(digits, radix, len, &powers)
+}
+
+fn from_digits_rec(digits: &[u32], radix: u32, len: usize, powers: &[Vec<u32>]) -> Vec<u32> {
+    if digits.len() < CONQUER_THRESHOLD * len {
+        return limbs_from_digits(digits.iter().copied(), radix);
+    }
+    // the low half is made of len * 2^level digits
+    let level = (0..powers.len())
+        .rev()
+        .find(|level| len << level <= digits.len() / 2)
+        .unwrap_or(0);
+    let (high, low) = digits.split_at(digits.len() - (len << level));
+    let mut ret = mul(&from_digits_rec(high, radix, len, powers), &powers[level]);
+    limbs_add(&mut ret, &from_digits_rec(low, radix, len, powers));
+    limbs_trim(&mut ret);
+    ret
+}
+
+// digits of a number in radix, least significant first (zero has no digit)
+pub(crate) fn to_digits(limbs: &[u32], radix: u32) -> Vec<u32> {
+    let (power, len) = radix_power(radix);
+    let mut powers = vec![vec![power]];
+    while powers.last().unwrap().len() * 2 <= limbs.len() {
+        let last = powers.last().unwrap();
+        powers.push(mul(last, last));
+    }
+    let mut ret = vec![];
+    to_digits_rec(limbs, radix, len, &powers, None, &mut ret);
+    ret
+}
+
+// push the digits of the number, padded with zeros to width digits if given
+fn to_digits_rec(
+    limbs: &[u32],
+    radix: u32,
+    len: usize,
+    powers: &[Vec<u32>],
+    width: Option<usize>,
+    out: &mut Vec<u32>,
+) {
+    let start = out.len();
+    if limbs.len() < CONQUER_THRESHOLD {
+        out.extend(limbs_to_digits(limbs, radix));
+    } else {
+        // the remainder is written on len * 2^level digits
+        let level = (0..powers.len())
+            .rev()
+            .find(|level| powers[*level].len() * 2 <= limbs.len() + 1)
+            .unwrap_or(0);
+        let (quotient, remainder) = divmod(limbs, &powers[level]);
+        let low = len << level;
+        to_digits_rec(&remainder, radix, len, powers, Some(low), out);
+        let high = width.map(|width| width - low);
+        to_digits_rec(&quotient, radix, len, powers, high, out);
+    }
+    if let Some(width) = width {
+        out.resize(start + width, 0);
+    }
+}
+
+// Karatsuba multiplication
+pub(crate) fn mul(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let (a, b) = if a.len() < b.len() { (b, a) } else { (a, b) };
+    if b.len() < KARATSUBA_THRESHOLD {
+        return limbs_mul(a, b);
+    }
+    if b.len() <= a.len() / 2 {
+        // unbalanced operands : a is cut in pieces of the length of b
+        let mut ret = vec![];
+        for (idx, piece) in a.chunks(b.len()).enumerate() {
+            add_shifted(&mut ret, &mul(trimmed(piece), b), idx * b.len());
+        }
+        limbs_trim(&mut ret);
+        return ret;
+    }
+    let half = a.len() / 2;
+    let (a0, a1) = split(a, half);
+    let (b0, b1) = split(b, half);
+    let z0 = mul(a0, b0);
+    let z2 = mul(a1, b1);
+    let mut sum_a = a0.to_vec();
+    limbs_add(&mut sum_a, a1);
+    let mut sum_b = b0.to_vec();
+    limbs_add(&mut sum_b, b1);
+    let mut z1 = mul(trimmed(&sum_a), trimmed(&sum_b));
+    limbs_sub(&mut z1, &z0);
+    limbs_sub(&mut z1, &z2);
+    let mut ret = z0;
+    add_shifted(&mut ret, &z1, half);
+    add_shifted(&mut ret, &z2, 2 * half);
+    limbs_trim(&mut ret);
+    ret
+}
+
+// quotient and remainder of n / d, d being not null : n is cut in pieces of the bit length of d,
+// each one being divided as in long division
+pub(crate) fn divmod(n: &[u32], d: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    if limbs_cmp(n, d) == Ordering::Less {
+        return (vec![], n.to_vec());
+    }
+    if d.len() < DIVISION_THRESHOLD {
+        return divmod_schoolbook(n, d);
+    }
+    let bits = bit_length(d);
+    let pieces = bit_length(n).div_ceil(bits);
+    let mut quotient = vec![];
+    let mut rem: Vec<u32> = vec![];
+    for idx in (0..pieces).rev() {
+        let mut piece = shl(&rem, bits);
+        limbs_add(&mut piece, &low_bits(&shr(n, idx * bits), bits));
+        limbs_trim(&mut piece);
+        let (digit, next) = div_2n_1n(&piece, d, bits);
+        quotient = shl(&quotient, bits);
+        limbs_add(&mut quotient, &digit);
+        limbs_trim(&mut quotient);
+        rem = next;
+    }
+    (quotient, rem)
+}
+
+// divide a by b, b having exactly bits bits and a being lower than b * 2^bits
+fn div_2n_1n(a: &[u32], b: &[u32], bits: usize) -> (Vec<u32>, Vec<u32>) {
+    if b.len() < DIVISION_THRESHOLD {
+        return divmod_schoolbook(a, b);
+    }
+    // an odd number of bits is made even
+    if bits % 2 == 1 {
+        let (quotient, rem) = div_2n_1n(&shl(a, 1), &shl(b, 1), bits + 1);
+        return (quotient, shr(&rem, 1));
+    }
+    let half = bits / 2;
+    let b1 = shr(b, half);
+    let b2 = low_bits(b, half);
+    let (q1, rem) = div_3n_2n(
+        &shr(a, bits),
+        &low_bits(&shr(a, half), half),
+        b,
+        &b1,
+        &b2,
+        half,
+    );
+    let (q2, rem) = div_3n_2n(&rem, &low_bits(a, half), b, &b1, &b2, half);
+    let mut quotient = shl(&q1, half);
+    limbs_add(&mut quotient, &q2);
+    limbs_trim(&mut quotient);
+    (quotient, rem)
+}
+
+// divide a12 * 2^half + a3 by b = b1 * 2^half + b2
+fn div_3n_2n(
+    a12: &[u32],
+    a3: &[u32],
+    b: &[u32],
+    b1: &[u32],
+    b2: &[u32],
+    half: usize,
+) -> (Vec<u32>, Vec<u32>) {
+    let (mut quotient, rem) = if limbs_cmp(&shr(a12, half), b1) == Ordering::Equal {
+        // the quotient is 2^half - 1
+        let mut rem = a12.to_vec();
+        limbs_add(&mut rem, b1);
+        limbs_trim(&mut rem);
+        limbs_sub(&mut rem, &shl(b1, half));
+        let mut quotient = shl(&[1], half);
+        limbs_sub(&mut quotient, &[1]);
+        (quotient, rem)
+    } else {
+        div_2n_1n(a12, b1, half)
+    };
+    let mut rem = shl(&rem, half);
+    limbs_add(&mut rem, a3);
+    limbs_trim(&mut rem);
+    let sub = mul(&quotient, b2);
+    // the quotient is at most 2 too large
+    while limbs_cmp(&rem, &sub) == Ordering::Less {
+        limbs_sub(&mut quotient, &[1]);
+        limbs_add(&mut rem, b);
+        limbs_trim(&mut rem);
+    }
+    limbs_sub(&mut rem, &sub);
+    (quotient, rem)
+}
+
+// long division of Knuth (algorithm D)
+fn divmod_schoolbook(n: &[u32], d: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    if limbs_cmp(n, d) == Ordering::Less {
+        return (vec![], n.to_vec());
+    }
+    if d.len() == 1 {
+        let mut quotient = n.to_vec();
+        let rem = limbs_divmod_small(&mut quotient, d[0]);
+        return (quotient, if rem == 0 { vec![] } else { vec![rem] });
+    }
+    // the highest bit of the divisor is set
+    let shift = d.last().unwrap().leading_zeros() as usize;
+    let d = shl(d, shift);
+    let mut u = shl(n, shift);
+    u.resize(n.len() + 1, 0);
+    let len_d = d.len();
+    let top = d[len_d - 1] as u64;
+    let second = d[len_d - 2] as u64;
+    let mut quotient = vec![0; u.len() - len_d];
+    for idx in (0..quotient.len()).rev() {
+        let num = ((u[idx + len_d] as u64) << 32) | u[idx + len_d - 1] as u64;
+        let mut qhat = num / top;
+        let mut rhat = num % top;
+        while qhat >> 32 > 0 || qhat * second > ((rhat << 32) | u[idx + len_d - 2] as u64) {
+            qhat -= 1;
+            rhat += top;
+            if rhat >> 32 > 0 {
+                break;
+            }
+        }
+        // multiply and subtract
+        let mut borrow: i64 = 0;
+        let mut carry: u64 = 0;
+        for (jdx, limb) in d.iter().enumerate() {
+            let product = qhat * *limb as u64 + carry;
+            carry = product >> 32;
+            let val = u[idx + jdx] as i64 - borrow - (product & 0xffff_ffff) as i64;
+            u[idx + jdx] = val as u32;
+            borrow = (val < 0) as i64;
+        }
+        let val = u[idx + len_d] as i64 - borrow - carry as i64;
+        u[idx + len_d] = val as u32;
+        if val < 0 {
+            // add back
+            qhat -= 1;
+            let mut carry: u64 = 0;
+            for (jdx, limb) in d.iter().enumerate() {
+                let sum = u[idx + jdx] as u64 + *limb as u64 + carry;
+                u[idx + jdx] = sum as u32;
+                carry = sum >> 32;
+            }
+            u[idx + len_d] = u[idx + len_d].wrapping_add(carry as u32);
+        }
+        quotient[idx] = qhat as u32;
+    }
+    limbs_trim(&mut quotient);
+    let rem = shr(&u[..len_d], shift);
+    (quotient, rem)
+}
+
+fn bit_length(a: &[u32]) -> usize {
+    a.len() * 32 - a.last().map_or(0, |limb| limb.leading_zeros() as usize)
+}
+
+// a * 2^bits
+fn shl(a: &[u32], bits: usize) -> Vec<u32> {
+    if a.is_empty() {
+        return vec![];
+    }
+    let (limbs, bits) = (bits / 32, bits % 32);
+    let mut ret = vec![0; limbs];
+    if bits == 0 {
+        ret.extend_from_slice(a);
+    } else {
+        let mut carry = 0;
+        for limb in a {
+            ret.push((limb << bits) | carry);
+            carry = limb >> (32 - bits);
+        }
+        ret.push(carry);
+    }
+    limbs_trim(&mut ret);
+    ret
+}
+
+// a / 2^bits
+fn shr(a: &[u32], bits: usize) -> Vec<u32> {
+    let (limbs, bits) = (bits / 32, bits % 32);
+    if limbs >= a.len() {
+        return vec![];
+    }
+    let a = &a[limbs..];
+    let mut ret: Vec<u32> = if bits == 0 {
+        a.to_vec()
+    } else {
+        (0..a.len())
+            .map(|idx| (a[idx] >> bits) | a.get(idx + 1).map_or(0, |next| next << (32 - bits)))
+            .collect()
+    };
+    limbs_trim(&mut ret);
+    ret
+}
+
+// a % 2^bits
+fn low_bits(a: &[u32], bits: usize) -> Vec<u32> {
+    let mut ret: Vec<u32> = a.iter().take(bits.div_ceil(32)).copied().collect();
+    if !bits.is_multiple_of(32) && ret.len() == bits.div_ceil(32) {
+        let last = ret.len() - 1;
+        ret[last] &= (1 << (bits % 32)) - 1;
+    }
+    limbs_trim(&mut ret);
+    ret
+}
+
+// low and high limbs of a number
+fn split(a: &[u32], half: usize) -> (&[u32], &[u32]) {
+    if a.len() <= half {
+        return (a, &[]);
+    }
+    (trimmed(&a[..half]), &a[half..])
+}
+
+fn trimmed(a: &[u32]) -> &[u32] {
+    let len = a.len() - a.iter().rev().take_while(|limb| **limb == 0).count();
+    &a[..len]
+}
+
+// a += b * 2^(32 * shift)
+fn add_shifted(a: &mut Vec<u32>, b: &[u32], shift: usize) {
+    if a.len() < shift + b.len() {
+        a.resize(shift + b.len(), 0);
+    }
+    let mut carry: u64 = 0;
+    let mut idx = shift;
+    while idx < a.len() && (idx < shift + b.len() || carry > 0) {
+        let val = a[idx] as u64 + b.get(idx - shift).map_or(0, |limb| *limb as u64) + carry;
+        a[idx] = val as u32;
+        carry = val >> 32;
+        idx += 1;
+    }
+    if carry > 0 {
+        a.push(carry as u32);
+    }
+}
@@ -13,6 +13,8 @@
 //!
 //! This library uses an extension of shift-adjust algorithm (and reversed shift-adjust) to convert numbers. Binary is used as a pivot radix. This method was described here : [Convert binary number to any base](https://www.edn.com/design/systems-design/4460458/Convert-binary-number-to-any-base).
 //!
+//! Integers fitting in 128 bits (most identifiers) are converted with native arithmetic, without intermediate allocation. The binary pivot is made of 32 bits words : digits are read and written by chunks, multiplying and dividing the words by the greatest power of the radix fitting in a word, so that numbers of 100000 digits are converted in less than a second. Above a few thousands digits, numbers are split in two halves by powers of the radix, each half being converted recursively (divide and conquer, with Karatsuba multiplication and Burnikel-Ziegler division) : numbers of millions of digits are converted in a few seconds. Between radices which are powers of two (bin, oct, hex, bibi...), each digit is a group of bits : bits are only regrouped, fractional part included, in linear time. Such numbers can even be converted digit by digit from any reader to any writer with `swap_stream`. Only numeral systems of the same radix and pairs where an input digit is made of whole output digits (hex to bibi, hex to bin, base32 to bin...) run in bounded memory : hex dumps of gigabytes can be written in bibi. The other pairs (hex to base32, bin to hex...) keep the bits of the integer part until its end, about half the size of a hex dump in memory.
+//!
 //! It was named after french singer (and also mathematician) [Boby Lapointe](https://en.wikipedia.org/wiki/Boby_Lapointe) who invented the [Bibi-binary system](https://en.wikipedia.org/wiki/Bibi-binary) in 1968.
 //!
 //! ## Exemple
@@ -48,8 +50,12 @@
 //!        let test = coder.swap("7d0").unwrap();
 //!        assert_eq!(test, "2000");
 
-use std::char;
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::convert::TryFrom;
 use std::fmt;
+use std::io::{Read, Write};
+use std::rc::Rc;
 
 extern crate indexmap;
 use indexmap::map::IndexMap;
@@ -58,35 +64,264 @@ use std::collections::HashMap;
 extern crate regex;
 use regex::Regex;
 
+extern crate unicode_normalization;
+use unicode_normalization::is_nfc;
+use unicode_normalization::UnicodeNormalization;
+
+extern crate unicode_segmentation;
+use unicode_segmentation::UnicodeSegmentation;
+
+#[cfg(feature = "bigint")]
+extern crate num_bigint;
+#[cfg(feature = "bigint")]
+use num_bigint::BigUint;
+
+mod roman;
+pub use roman::{Roman, RomanNotation};
+
+mod words;
+pub use words::{Language, Scale, Words};
+
+mod registry;
+pub use registry::Registry;
+
+mod huge;
+
+mod stream;
+
 #[derive(Debug)]
 pub enum BibiError {
-    /// Malformed numeral system : all digits must have the same length and be unique
+    /// Malformed numeral system : digits must be unique and not empty
     BadNumeralSystem,
+    /// Digits of the numeral system do not form a uniquely decodable code : the digits given can be read in several ways
+    AmbiguousDigits(Vec<String>),
     /// One digit given in the entry was not found in numeral system
     EntryMismatchWithNumeralSystem,
     RegexMismatchWithEntry,
     /// Non existent pre-defined numeral system
     BadTagNumeralSystem,
     /// Bad regular expression
-    BadRegularExpression
+    BadRegularExpression,
+    /// File can not be read or written
+    BadFile,
+    /// The number is too large for the requested integer type
+    Overflow,
+    /// The number is negative and the output numeral system has no minus sign, or a bit pattern is read with a minus sign
+    NegativeNotSupported,
+    /// The number has a fractional part and the numeral system has no radix point or only reads integers
+    FractionNotSupported,
+    /// The denominator of a rational number is zero
+    DivisionByZero,
+    /// The number can not be written by the output codec : zero or too large number in Roman numerals
+    NotRepresentable,
+    /// The name is already registered
+    DuplicateName(String),
+    /// The prefix is already used by the registered numeral system given
+    PrefixCollision(String),
+    /// The codec of the entry can not be found out : several candidates are likely
+    AmbiguousNumeralSystem(Vec<String>),
+    /// The numbers can not be converted digit by digit (see `BibiCoder::can_stream`)
+    NotStreamable,
+}
+
+// Trie of the digits of a numeral system, one edge per grapheme cluster
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct DigitTrie {
+    nodes: Vec<TrieNode>,
+    // no digit is the beginning of another one
+    prefix_free: bool,
+    // digits made of one ascii character, found without hashing
+    ascii: Vec<Option<u32>>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct TrieNode {
+    children: HashMap<String, usize>,
+    value: Option<u32>,
+}
+
+impl DigitTrie {
+    fn new() -> DigitTrie {
+        DigitTrie {
+            nodes: vec![TrieNode::default()],
+            prefix_free: true,
+            ascii: vec![None; 128],
+        }
+    }
+
+    fn insert(&mut self, digit: &str, value: u32) {
+        if digit.len() == 1 && digit.is_ascii() {
+            self.ascii[digit.as_bytes()[0] as usize] = Some(value);
+        }
+        let mut node = 0;
+        for grapheme in digit.graphemes(true) {
+            if self.nodes[node].value.is_some() {
+                self.prefix_free = false;
+            }
+            node = match self.nodes[node].children.get(grapheme) {
+                Some(next) => *next,
+                None => {
+                    self.nodes.push(TrieNode::default());
+                    let next = self.nodes.len() - 1;
+                    self.nodes[node]
+                        .children
+                        .insert(String::from(grapheme), next);
+                    next
+                }
+            };
+        }
+        if !self.nodes[node].children.is_empty() {
+            self.prefix_free = false;
+        }
+        self.nodes[node].value = Some(value);
+    }
+
+    // returns the node reached by following the graphemes, if any
+    fn walk(&self, graphemes: &[&str]) -> Option<usize> {
+        let mut node = 0;
+        for grapheme in graphemes {
+            node = *self.nodes[node].children.get(*grapheme)?;
+        }
+        Some(node)
+    }
+
+    // no digit is the beginning of another one : digits can be read one by one
+    fn is_prefix_free(&self) -> bool {
+        self.prefix_free
+    }
+
+    // digit made of one ascii character
+    fn ascii_digit(&self, byte: u8) -> Option<u32> {
+        self.ascii.get(byte as usize).copied().flatten()
+    }
+
+    // all the digits strictly below a node
+    fn descendants(&self, node: usize) -> Vec<u32> {
+        let mut ret = vec![];
+        let mut stack: Vec<usize> = self.nodes[node].children.values().cloned().collect();
+        while let Some(next) = stack.pop() {
+            if let Some(value) = self.nodes[next].value {
+                ret.push(value);
+            }
+            stack.extend(self.nodes[next].children.values());
+        }
+        ret
+    }
+
+    // split the graphemes into digits : from each position reached, every digit of the trie matching
+    // the following graphemes reaches a new position, the digits being read back from the end
+    // as the code is uniquely decodable, there is at most one way to read the entry
+    fn tokenize(&self, graphemes: &[&str]) -> Option<Vec<u32>> {
+        let len = graphemes.len();
+        // for each reachable position, the position and the digit which lead to it
+        let mut back: Vec<Option<(usize, u32)>> = vec![None; len + 1];
+        let mut reached = vec![false; len + 1];
+        reached[0] = true;
+        for start in 0..len {
+            if !reached[start] {
+                continue;
+            }
+            let mut node = 0;
+            for (idx, grapheme) in graphemes.iter().enumerate().skip(start) {
+                node = match self.nodes[node].children.get(*grapheme) {
+                    Some(next) => *next,
+                    None => break,
+                };
+                if let Some(value) = self.nodes[node].value {
+                    if !reached[idx + 1] {
+                        reached[idx + 1] = true;
+                        back[idx + 1] = Some((start, value));
+                    }
+                }
+            }
+        }
+        if !reached[len] {
+            return None;
+        }
+        let mut ret = vec![];
+        let mut pos = len;
+        while pos > 0 {
+            let (start, value) = back[pos]?;
+            ret.push(value);
+            pos = start;
+        }
+        ret.reverse();
+        Some(ret)
+    }
+}
+
+// how digits stand for numbers : the numerations other than the standard one exclude each other
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+enum Numeration {
+    // digits stand for 0..radix-1
+    #[default]
+    Standard,
+    // digits stand for 1..radix, without zero digit
+    Bijective,
+    // the radix is -len, every integer being written without sign
+    NegativeBase,
+    // values of the digits, and digit written for each residue modulo the radix
+    Values {
+        values: Vec<i32>,
+        residues: Vec<usize>,
+    },
+    // digits of each position, least significant first, and numeral system of what is left after the
+    // positions
+    Mixed {
+        positions: Vec<NumeralSystem>,
+        head: Option<Box<NumeralSystem>>,
+    },
 }
 
-/// Define a numeral system by enumerating all the digits. The first digit is zero. The radix is equal to the number of digits. One digit can have any number of characters but all digits must have the same length.
-#[derive(Debug, Clone, Default,PartialEq, Eq)]
+/// Define a numeral system by enumerating all the digits. The first digit is zero. The radix is equal to the number of digits. One digit can have any number of characters and digits can have different lengths as long as any number can be read in only one way (the digits must form a uniquely decodable code, for example a prefix-free one).
+///
+/// A separator can be placed between the digits (for example `Fi-Xa-Du` or `12:34:56`). Digits are then delimited by the separator and can be any strings.
+///
+/// Digits can be made of any unicode graphemes (emoji, greek, CJK, combining sequences...). The length of a digit is counted in grapheme clusters and digits are stored NFC-normalized.
+///
+/// A number can have a fractional part written after a radix point (`3.14159`, `0x1.8`). By default the radix point is "." unless it is used in the digits. It can be changed with `set_radix_point`.
+///
+/// A numeral system with only one digit is unary (tally marks) : 5 is written `|||||`, zero is written without digits and a marker can stand for each group of five marks (see `set_unary_group`). Numbers longer than the length limit (see `set_length_limit`) can not be written : `BibiError::Overflow` is returned.
+///
+/// A mixed-radix numeral system gives each position its own digits (see `new_mixed`), for example `d:hh:mm:ss` durations or the factorial number system.
+///
+/// A number can start with a sign, placed before the prefix (`-0x1f`, `+255`, `−HAHO`). By default "-" and "−" are minus signs and "+" a plus sign, except when they conflict with the digits or the prefix. Signs can be changed with `set_signs`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct NumeralSystem {
     prefix: String,
-    len_digit: usize,
+    // the first minus sign is used to write negative numbers
+    minus: Vec<String>,
+    plus: Vec<String>,
+    // written between two digits, empty if none
+    separator: String,
+    // written between integer and fractional parts, empty if none
+    radix_point: String,
+    // written between numerator and denominator of rational numbers, empty if none
+    fraction_bar: String,
+    numeration: Numeration,
+    // written for five marks in unary systems, empty if none
+    unary_group: String,
+    // maximum number of marks written in unary systems
+    length_limit: usize,
     digits: IndexMap<String, u32>,
     // maintains reverse list of digits to find them quickly
     revdigits: IndexMap<u32, String>,
+    // used to split entries into digits
+    trie: DigitTrie,
 }
 
+// default maximum number of marks written by unary numeral systems
+const DEFAULT_LENGTH_LIMIT: usize = 1 << 20;
+
 impl NumeralSystem {
     // static method to find out a numeral system by its prefix given a number
+    // (only if one prefix matches, Registry::autodetect picks the longest one)
     pub fn autodetect<'a>(number: &str, nums: Vec<&'a NumeralSystem>) -> Option<&'a NumeralSystem> {
         let res: Vec<&'a NumeralSystem> = nums
             .into_iter()
-            .filter(|ns| (ns.prefix.len() > 0) && (ns.prefix[..] == number[0..ns.prefix.len()]))
+            .filter(|ns| {
+                !ns.prefix.is_empty() && ns.strip_sign(number).1.starts_with(&ns.prefix[..])
+            })
             .collect();
         if res.len() == 1 {
             return Some(res[0]);
@@ -98,16 +333,83 @@ impl NumeralSystem {
     /// - Exemple for decimal system entry must be vec!(vec!("0", "1", "2", "3", "4", "5", "6", "7", "8", "9"))
     ///
     pub fn new(prefix: &str, entry: Vec<Vec<&str>>) -> Result<NumeralSystem, BibiError> {
-        return NumeralSystem::new_rec(prefix, &entry, 0);
+        NumeralSystem::new_with_separator(prefix, "", entry)
+    }
+
+    /// Same as ::new but digits are delimited by the separator.
+    /// - Exemple for sexagesimal system with 00 to 59 digits and ":" separator, 3723 will be written 01:02:03
+    ///
+    pub fn new_with_separator(
+        prefix: &str,
+        separator: &str,
+        entry: Vec<Vec<&str>>,
+    ) -> Result<NumeralSystem, BibiError> {
+        let mut num = NumeralSystem::new_rec(prefix, &entry, 0)?;
+        num.set_separator(separator)?;
+        num.set_default_symbols()?;
+        Ok(num)
+    }
+
+    /// Returns a mixed-radix numeral system : each position has its own digits, and so its own radix. Positions are given from the most significant one, as they are written. What is left after the positions is written in the head numeral system if any, or else with the digits of the most significant position.
+    /// - Exemple for durations `d:hh:mm:ss` : separator ":", positions 00..23, 00..59, 00..59 and decimal head, 93784 seconds being written 1:02:03:04
+    ///
+    /// Digits of a position are read from the end of the number, so that without separator no digit of a position should end another one.
+    pub fn new_mixed(
+        prefix: &str,
+        separator: &str,
+        positions: Vec<Vec<&str>>,
+        head: Option<NumeralSystem>,
+    ) -> Result<NumeralSystem, BibiError> {
+        let mut built = vec![];
+        for alphabet in positions.into_iter().rev() {
+            built.push(NumeralSystem::new_with_separator(
+                "",
+                separator,
+                vec![alphabet],
+            )?);
+        }
+        // the most significant position is repeated if there is no head
+        match built.last() {
+            Some(last) if last.len() >= 2 || head.is_some() => {}
+            _ => return Err(BibiError::BadNumeralSystem),
+        }
+        let mut num = NumeralSystem {
+            prefix: String::from(prefix),
+            separator: String::from(separator),
+            numeration: Numeration::Mixed {
+                positions: built,
+                head: head.map(Box::new),
+            },
+            ..Default::default()
+        };
+        if !separator.is_empty() && num.symbol_conflicts(separator) {
+            return Err(BibiError::BadNumeralSystem);
+        }
+        num.set_default_symbols()?;
+        Ok(num)
+    }
+
+    // default signs, radix point and fraction bar, unless they can be mistaken for digits
+    fn set_default_symbols(&mut self) -> Result<(), BibiError> {
+        let minus: Vec<&str> = vec!["-", "−"]
+            .into_iter()
+            .filter(|sign| !self.sign_conflicts(sign))
+            .collect();
+        let plus: Vec<&str> = vec!["+"]
+            .into_iter()
+            .filter(|sign| !self.sign_conflicts(sign))
+            .collect();
+        self.set_signs(minus, plus)?;
+        self.set_default_radix_point();
+        if self.set_fraction_bar("/").is_err() {
+            self.fraction_bar = String::new();
+        }
+        Ok(())
     }
 
+    /// Returns true if the string can be used in a digit : any unicode char except whitespaces and control chars.
     pub fn char_authorized(s: &str) -> bool {
-        s.chars().all(|c| {
-            c.is_ascii_alphanumeric() ||
-            c == '-' ||
-            c == '#' ||
-            c == '_'
-        })
+        s.chars().all(|c| !c.is_whitespace() && !c.is_control())
     }
 
     /// Same as ::new but from vec of strings.
@@ -122,12 +424,70 @@ impl NumeralSystem {
         NumeralSystem::new(&prefix[..], entry_str)
     }
 
+    /// Same as ::new_with_separator but from vec of strings.
+    pub fn new_from_strings_with_separator(
+        prefix: String,
+        separator: String,
+        entry: Vec<Vec<String>>,
+    ) -> Result<NumeralSystem, BibiError> {
+        let entry_str: Vec<Vec<&str>> = entry
+            .iter()
+            .map(|v| v.iter().map(|s| &**s).collect())
+            .collect();
+        NumeralSystem::new_with_separator(&prefix[..], &separator[..], entry_str)
+    }
+
+    /// Returns a pre-defined numeral system (see get_tags), or one of the mixed-radix systems :
+    /// - factoradic for the factorial number system, with digits 0..9A..Z on 36 positions
+    /// - duration for seconds written d:hh:mm:ss
+    ///
+    /// The numeral system is cloned from `Registry::builtin`.
     pub fn new_from_tag(tag: &str) -> Result<NumeralSystem, BibiError> {
+        Registry::builtin()
+            .get(tag)
+            .and_then(|codec| codec.numeral_system().cloned())
+            .ok_or(BibiError::BadTagNumeralSystem)
+    }
+
+    // build a pre-defined numeral system, only called once by the builtin registry
+    fn build_from_tag(tag: &str) -> Result<NumeralSystem, BibiError> {
+        match tag {
+            "factoradic" => {
+                let digits: Vec<String> = (b'0'..=b'9')
+                    .chain(b'A'..=b'Z')
+                    .map(|c| (c as char).to_string())
+                    .collect();
+                let positions: Vec<Vec<&str>> = (1..=digits.len())
+                    .rev()
+                    .map(|radix| digits[..radix].iter().map(|digit| &digit[..]).collect())
+                    .collect();
+                return NumeralSystem::new_mixed("", "", positions, None);
+            }
+            "duration" => {
+                let digits: Vec<String> = (0..60).map(|digit| format!("{:02}", digit)).collect();
+                let alphabet =
+                    |radix: usize| digits[..radix].iter().map(|digit| &digit[..]).collect();
+                let head = NumeralSystem::build_from_tag("dec")?;
+                return NumeralSystem::new_mixed(
+                    "",
+                    ":",
+                    vec![alphabet(24), alphabet(60), alphabet(60)],
+                    Some(head),
+                );
+            }
+            _ => {}
+        }
         let tags = NumeralSystem::get_tags();
         if tags.contains_key(tag) {
             let prefix = tags[tag].0.clone();
             let vecd = tags[tag].1.clone();
-            Ok(NumeralSystem::new_from_strings(prefix, vecd)?)
+            let mut num = NumeralSystem::new_from_strings(prefix, vecd)?;
+            match tag {
+                "excel" => num.set_bijective(true)?,
+                "negabin" | "negadec" => num.set_negative_base(true)?,
+                _ => {}
+            }
+            Ok(num)
         } else {
             Err(BibiError::BadTagNumeralSystem)
         }
@@ -136,21 +496,15 @@ impl NumeralSystem {
     // internal method to build system from vec of vec
     fn new_rec(
         prefix: &str,
-        entry: &Vec<Vec<&str>>,
+        entry: &[Vec<&str>],
         index: usize,
     ) -> Result<NumeralSystem, BibiError> {
-        let mut len_digit;
         let mut digits: IndexMap<String, u32> = IndexMap::new();
         let mut revdigits: IndexMap<u32, String> = IndexMap::new();
 
-        let first_entry = &entry[index];
-
-        match first_entry.first() {
-            None => return Err(BibiError::BadNumeralSystem),
-            Some(digit) => len_digit = digit.len(),
-        }
+        let first_entry: Vec<String> = entry[index].iter().map(|d| d.nfc().collect()).collect();
 
-        if len_digit == 0 {
+        if first_entry.is_empty() {
             return Err(BibiError::BadNumeralSystem);
         }
 
@@ -160,26 +514,30 @@ impl NumeralSystem {
         }
 
         let mut cpt: u32 = 0;
-        for digit in first_entry {
-            if digit.len() != len_digit {
-                return Err(BibiError::BadNumeralSystem);
-            }
-            if ! NumeralSystem::char_authorized(digit) {
+        for digit in first_entry.iter() {
+            if digit.is_empty() || !NumeralSystem::char_authorized(digit) {
                 return Err(BibiError::BadNumeralSystem);
             }
-            if let Some(num) = sub_num_sys {
+            let len_digit = digit.graphemes(true).count();
+            let mut combined: Vec<(String, usize)> = vec![];
+            if let Some(ref num) = sub_num_sys {
                 for digit2 in num.digits.keys() {
-                    digits
-                        .entry(String::from(*digit) + &String::from(&digit2[..]))
-                        .or_insert(cpt);
-                    revdigits.insert(cpt, String::from(*digit) + &String::from(&digit2[..]));
-                    cpt = cpt + 1;
+                    combined.push((
+                        digit.clone() + digit2,
+                        len_digit + digit2.graphemes(true).count(),
+                    ));
                 }
-                sub_num_sys = Some(num);
             } else {
-                digits.entry(String::from(*digit)).or_insert(cpt);
-                revdigits.insert(cpt, String::from(*digit));
-                cpt = cpt + 1;
+                combined.push((digit.clone(), len_digit));
+            }
+            for (digit, len_combined) in combined {
+                // a combining char at the start of a sub digit would merge graphemes
+                if digit.graphemes(true).count() != len_combined {
+                    return Err(BibiError::BadNumeralSystem);
+                }
+                digits.entry(digit.clone()).or_insert(cpt);
+                revdigits.insert(cpt, digit);
+                cpt += 1;
             }
         }
 
@@ -188,18 +546,114 @@ impl NumeralSystem {
             return Err(BibiError::BadNumeralSystem);
         }
 
-        if let Some(num) = sub_num_sys {
-            len_digit = len_digit + num.len_digit;
-        }
-
         Ok(NumeralSystem {
             prefix: String::from(prefix),
-            len_digit: len_digit,
-            digits: digits,
-            revdigits: revdigits,
+            minus: vec![],
+            plus: vec![],
+            separator: String::new(),
+            radix_point: String::new(),
+            fraction_bar: String::new(),
+            numeration: Numeration::Standard,
+            unary_group: String::new(),
+            length_limit: DEFAULT_LENGTH_LIMIT,
+            digits,
+            revdigits,
+            trie: DigitTrie::default(),
         })
     }
 
+    // build the trie used to read numbers and check that digits can not be read in several ways
+    fn build_trie(&mut self) -> Result<(), BibiError> {
+        let mut trie = DigitTrie::new();
+        for (digit, value) in self.digits.iter() {
+            trie.insert(digit, *value);
+        }
+        self.trie = trie;
+        // with a separator, digits are delimited and can not be misread
+        if !self.separator.is_empty() {
+            return Ok(());
+        }
+        if let Some(ambiguous) = self.find_ambiguous_digits() {
+            return Err(BibiError::AmbiguousDigits(ambiguous));
+        }
+        Ok(())
+    }
+
+    // split a number (without prefix) into digits
+    fn read_digits(&self, entry: &str) -> Option<Vec<u32>> {
+        if self.separator.is_empty() {
+            if !NumeralSystem::char_authorized(entry) {
+                return None;
+            }
+            let graphemes: Vec<&str> = entry.graphemes(true).collect();
+            self.trie.tokenize(&graphemes)
+        } else {
+            entry
+                .split(&self.separator[..])
+                .map(|digit| self.digits.get(digit).cloned())
+                .collect()
+        }
+    }
+
+    // Sardinas-Patterson test : follow the dangling suffixes left when a digit is a prefix of
+    // another one. The code is ambiguous as soon as a dangling suffix is a digit itself.
+    // Returns the digits involved in the ambiguity.
+    fn find_ambiguous_digits(&self) -> Option<Vec<String>> {
+        let graphemes: Vec<Vec<&str>> = self
+            .revdigits
+            .values()
+            .map(|digit| digit.graphemes(true).collect())
+            .collect();
+
+        // dangling suffixes to explore with the digits that produced them
+        let mut queue: Vec<(Vec<&str>, Vec<u32>)> = vec![];
+        let mut seen: HashSet<Vec<&str>> = HashSet::new();
+
+        for (idx, digit) in graphemes.iter().enumerate() {
+            let node = self.trie.walk(digit)?;
+            for longer in self.trie.descendants(node) {
+                let suffix = graphemes[longer as usize][digit.len()..].to_vec();
+                queue.push((suffix, vec![idx as u32, longer]));
+            }
+        }
+
+        while let Some((suffix, origin)) = queue.pop() {
+            if !seen.insert(suffix.clone()) {
+                continue;
+            }
+            // digits which are prefixes of the suffix
+            let mut node = 0;
+            for (len, grapheme) in suffix.iter().enumerate() {
+                node = match self.trie.nodes[node].children.get(*grapheme) {
+                    Some(next) => *next,
+                    None => break,
+                };
+                if let Some(value) = self.trie.nodes[node].value {
+                    let mut origin = origin.clone();
+                    origin.push(value);
+                    if len + 1 == suffix.len() {
+                        let mut ret: Vec<String> = origin
+                            .iter()
+                            .map(|value| self.revdigits[value].clone())
+                            .collect();
+                        ret.dedup();
+                        return Some(ret);
+                    }
+                    queue.push((suffix[len + 1..].to_vec(), origin));
+                }
+            }
+            // digits beginning with the suffix
+            if let Some(node) = self.trie.walk(&suffix) {
+                for longer in self.trie.descendants(node) {
+                    let mut origin = origin.clone();
+                    origin.push(longer);
+                    queue.push((graphemes[longer as usize][suffix.len()..].to_vec(), origin));
+                }
+            }
+        }
+        None
+    }
+
     /// Returns pre-defined numeral systems :
     /// - dec for decimal
     /// - hex for hexadecimal
@@ -207,10 +661,14 @@ impl NumeralSystem {
     /// - bin for binary
     /// - budu for a test system easy to read
     /// - base58 for base58 as used in bitcoin
+    /// - utf8 for a test system made of unicode symbols
+    /// - excel for bijective base 26 as used in spreadsheet columns (A, ..., Z, AA, ...)
+    /// - unary for tally marks
+    /// - negabin for base -2
+    /// - negadec for base -10
     ///
-    /// UTILISER lazy-static
+    /// They are built once by `Registry::builtin`.
     fn get_tags() -> IndexMap<&'static str, (String, Vec<Vec<String>>)> {
-
         let mut tags: IndexMap<&'static str, (String, Vec<Vec<String>>)> = IndexMap::new();
         tags.insert(
             "bin",
@@ -399,361 +857,4453 @@ impl NumeralSystem {
                 ]],
             ),
         );
+        tags.insert(
+            "utf8",
+            (
+                String::from(""),
+                vec![
+                    vec![
+                        String::from("☰"),
+                        String::from("☱"),
+                        String::from("☲"),
+                        String::from("☳"),
+                        String::from("☴"),
+                        String::from("☵"),
+                        String::from("☶"),
+                        String::from("☷"),
+                    ],
+                    vec![
+                        String::from("♠"),
+                        String::from("♣"),
+                        String::from("♥"),
+                        String::from("♦"),
+                    ],
+                ],
+            ),
+        );
+        tags.insert(
+            "excel",
+            (
+                String::from(""),
+                vec![(b'A'..=b'Z').map(|c| (c as char).to_string()).collect()],
+            ),
+        );
+        tags.insert("unary", (String::from(""), vec![vec![String::from("|")]]));
+        tags.insert(
+            "negabin",
+            (
+                String::from(""),
+                vec![vec![String::from("0"), String::from("1")]],
+            ),
+        );
+        tags.insert(
+            "negadec",
+            (
+                String::from(""),
+                vec![(b'0'..=b'9').map(|c| (c as char).to_string()).collect()],
+            ),
+        );
         tags
     }
 
+    /// Prefixes of the pre-defined numeral systems with their tag (see `Registry::get_prefixes`)
     pub fn get_prefixes_from_tags() -> HashMap<String, String> {
-        let mut ret = HashMap::new();
-        let tags = NumeralSystem::get_tags();
-        for (tag, tuple) in tags.iter() {
-            let pref = tuple.0.to_string();
-            if !pref.is_empty() {
-                ret.insert(pref, tag.to_string());
-            }
-        }
-        ret
+        Registry::builtin().get_prefixes()
     }
 
-    /// Returns the legth of a digit (all digits have the same length)
+    /// Returns the number of digits
     pub fn len(&self) -> usize {
         self.digits.len()
     }
 
-    /// Return the radix of this numeral system (= number of digits in numeral system)
-    pub fn radix(&self) -> usize {
-        self.len()
+    // number of bits written by one digit, for radices which are powers of two
+    fn bits_per_digit(&self) -> Option<usize> {
+        let radix = self.radix()?;
+        if radix >= 2 && radix.is_power_of_two() {
+            Some(radix.trailing_zeros() as usize)
+        } else {
+            None
+        }
     }
 
-    pub fn get_prefix(&self) -> String {
-        self.prefix.clone()
+    // number of bits of a digit when numbers are read and written by regrouping bits : radix a power
+    // of two, digits standing for their position (no digit values)
+    fn bit_group(&self) -> Option<usize> {
+        match self.numeration {
+            Numeration::Values { .. } => None,
+            _ => self.bits_per_digit(),
+        }
     }
 
-    pub fn set_prefix(&mut self, prefix: &str) {
-        self.prefix = String::from(prefix);
+    // numbers are read (if delimited) and written digit by digit, most significant first : standard
+    // positional numeration, digits being delimited by the separator or prefix-free
+    fn streams(&self, delimited: bool) -> bool {
+        self.numeration == Numeration::Standard
+            && !self.is_unary()
+            && (!delimited || !self.separator.is_empty() || self.trie.is_prefix_free())
     }
-}
 
-impl fmt::Display for NumeralSystem {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let mut disp = String::from("");
-        let mut sep = String::from("");
-        for digit in self.digits.keys() {
-            disp = disp + &sep + digit;
-            sep = String::from(", ");
+    // number of digits needed to write one byte, for radices 2, 4, 16 and 256
+    fn digits_per_byte(&self) -> Option<usize> {
+        match self.radix()? {
+            2 => Some(8),
+            4 => Some(4),
+            16 => Some(2),
+            256 => Some(1),
+            _ => None,
         }
-        write!(f, "{}", disp)
     }
-}
 
-/// Convert any number from one numeral system to the other.
-#[derive(Debug)]
-pub struct BibiCoder {
-    numsys_in: NumeralSystem,
-    numsys_out: NumeralSystem,
-}
+    /// Returns true if the numeral system has no digit
+    pub fn is_empty(&self) -> bool {
+        self.digits.is_empty()
+    }
 
-impl BibiCoder {
-    /// Build a coder from numsys_in numeral system to numsys_out
-    pub fn new(numsys_in: NumeralSystem, numsys_out: NumeralSystem) -> BibiCoder {
-        BibiCoder {
-            numsys_in,
-            numsys_out,
+    /// Return the radix of this numeral system (= number of digits in numeral system, unless digit values are given). Mixed-radix systems have no single radix and give None.
+    pub fn radix(&self) -> Option<usize> {
+        match self.numeration {
+            Numeration::Mixed { .. } => None,
+            Numeration::Values { ref residues, .. } => Some(residues.len()),
+            _ => Some(self.len()),
         }
     }
 
-    /// find all numbers in entry from regular expression
-    pub fn extract_numbers(entry: &str, reg: &str) -> Result<Vec<String>, BibiError> {
-        let mut numbers: Vec<String> = vec![];
-
-        if !reg.is_empty() {
-            let re: Regex = match Regex::new(&reg[..]) {
-                Ok(res) => res,
-                Err(_) => return Err(BibiError::BadRegularExpression),
-            };
-            let caps = match re.captures(entry) {
-                Some(caps) => caps,
-                None => return Err(BibiError::RegexMismatchWithEntry),
-            };
-            for number in caps.iter().skip(1) {
-                if let Some(numberok) = number {
-                    numbers.push(numberok.as_str().to_string());
-                }
-            }
-        } else {
-            numbers.push(entry.to_string());
-        }
+    // radix of the digits of a numeral system which is not mixed-radix, mixed-radix systems being
+    // handled apart by every caller
+    fn positional_radix(&self) -> usize {
+        self.radix()
+            .expect("mixed-radix numeral systems have no single radix")
+    }
 
-        Ok(numbers)
+    pub fn get_prefix(&self) -> String {
+        self.prefix.clone()
     }
 
-    /// Swap an natural number coded in numsys_in system to numsys_out
-    pub fn swap(&self, entry: &str) -> Result<String, BibiError> {
-        let pivot = self.tsujda_tfihs(entry)?;
-        self.shift_adjust(pivot)
+    pub fn set_prefix(&mut self, prefix: &str) {
+        self.prefix = String::from(prefix);
     }
 
-    // compute BCD  numbers into binary
-    fn tsujda_tfihs(&self, entry: &str) -> Result<Vec<bool>, BibiError> {
+    /// Returns minus signs and plus signs
+    pub fn get_signs(&self) -> (Vec<String>, Vec<String>) {
+        (self.minus.clone(), self.plus.clone())
+    }
 
-        // erase the prefix if present
-        let rel_entry: &str;
-        if (self.numsys_in.prefix.len() > 0)
-            && (entry.len() > self.numsys_in.prefix.len())
-            && (self.numsys_in.prefix[..] == entry[0..self.numsys_in.prefix.len()])
+    /// Set the signs which can be written before a number (and before its prefix). The first minus sign is used to write negative numbers. A sign and a digit (or the prefix) can not be the beginning of one another. No sign at all means that only natural numbers can be read.
+    pub fn set_signs(&mut self, minus: Vec<&str>, plus: Vec<&str>) -> Result<(), BibiError> {
+        let minus: Vec<String> = minus.iter().map(|sign| sign.nfc().collect()).collect();
+        let plus: Vec<String> = plus.iter().map(|sign| sign.nfc().collect()).collect();
+        if minus
+            .iter()
+            .chain(plus.iter())
+            .any(|sign| self.sign_conflicts(sign))
         {
-            rel_entry = &entry[self.numsys_in.prefix.len()..];
-        } else {
-            rel_entry = entry;
+            return Err(BibiError::BadNumeralSystem);
         }
+        self.minus = minus;
+        self.plus = plus;
+        Ok(())
+    }
 
-        let radix = self.numsys_in.len() as u32;
-
-        let mut bcd: Vec<u32> = vec![];
-        let mut pivot: Vec<bool> = vec![];
-
-        // compute bcd numbers from the entry
+    pub fn get_radix_point(&self) -> String {
+        self.radix_point.clone()
+    }
 
-        if rel_entry.len() == 0 {
-            return Err(BibiError::EntryMismatchWithNumeralSystem);
+    /// Set the radix point written between the integer and the fractional parts of a number. The radix point can not be part of a digit or be the separator. An empty radix point means that only integers can be read.
+    pub fn set_radix_point(&mut self, radix_point: &str) -> Result<(), BibiError> {
+        let radix_point: String = radix_point.nfc().collect();
+        if !radix_point.is_empty()
+            && (self.numeration != Numeration::Standard
+                || self.is_unary()
+                || radix_point == self.separator
+                || radix_point == self.fraction_bar
+                || self.symbol_conflicts(&radix_point))
+        {
+            return Err(BibiError::BadNumeralSystem);
         }
+        self.radix_point = radix_point;
+        Ok(())
+    }
 
-        if rel_entry.len() as f32 % self.numsys_in.len_digit as f32 > 0.0 {
-            return Err(BibiError::EntryMismatchWithNumeralSystem);
+    // "." unless it can be mistaken for a digit or the numeral system reads only integers
+    fn set_default_radix_point(&mut self) {
+        if self.set_radix_point(".").is_err() {
+            self.radix_point = String::new();
         }
+    }
 
-        if ! NumeralSystem::char_authorized(rel_entry) {
-            return Err(BibiError::EntryMismatchWithNumeralSystem);
-        }
+    pub fn is_bijective(&self) -> bool {
+        self.numeration == Numeration::Bijective
+    }
 
-        for i in 0..(rel_entry.len() / self.numsys_in.len_digit) {
-            let digit = String::from(
-                &rel_entry[i * self.numsys_in.len_digit
-                    ..(i * self.numsys_in.len_digit) + self.numsys_in.len_digit],
-            );
-            let digidx: u32 = match self.numsys_in.digits.get(&digit) {
-                Some(d) => *d,
-                None => return Err(BibiError::EntryMismatchWithNumeralSystem),
-            };
-            bcd.push(digidx);
-        }
+    /// Use bijective numeration : digits stand for 1..radix instead of 0..radix-1 (A..Z for 1..26, 27 being AA as in spreadsheet columns) and zero is written without digits. Bijective numeral systems have no radix point, the default one coming back with standard numeration.
+    pub fn set_bijective(&mut self, bijective: bool) -> Result<(), BibiError> {
+        self.switch_numeration(Numeration::Bijective, bijective)
+    }
 
-        // reverse shift adjust
-        loop {
-            let mut end = true;
-            let mut rel = 0;
-            for idx in 0..bcd.len() {
-                let nb = bcd[idx] + rel * radix;
-                rel = nb % 2;
-                bcd[idx] = nb / 2;
-                end = if bcd[idx] > 0 { false } else { end };
-            }
-            pivot.insert(0, if rel == 1 { true } else { false });
-            if end {
-                break;
+    // turn a numeration on or off. Numerations other than the standard one are only turned on from the
+    // standard one (mixed-radix systems being built as such), need two digits at least and have no radix
+    // point, the default one coming back with standard numeration.
+    fn switch_numeration(&mut self, numeration: Numeration, on: bool) -> Result<(), BibiError> {
+        let same = std::mem::discriminant(&self.numeration) == std::mem::discriminant(&numeration);
+        if !on {
+            if same {
+                self.numeration = Numeration::Standard;
+                self.set_default_radix_point();
             }
+            return Ok(());
+        }
+        if self.len() < 2 || !(same || self.numeration == Numeration::Standard) {
+            return Err(BibiError::BadNumeralSystem);
         }
+        self.numeration = numeration;
+        self.radix_point = String::new();
+        Ok(())
+    }
 
-        Ok(pivot)
+    pub fn is_unary(&self) -> bool {
+        self.len() == 1
+    }
+
+    pub fn get_unary_group(&self) -> String {
+        self.unary_group.clone()
+    }
+
+    /// Set the marker written for each group of five marks of a unary numeral system (`卌卌||` for 12), empty for none. Numbers can be read with or without groups.
+    pub fn set_unary_group(&mut self, marker: &str) -> Result<(), BibiError> {
+        let marker: String = marker.nfc().collect();
+        if !marker.is_empty()
+            && (!self.is_unary()
+                || !NumeralSystem::char_authorized(&marker)
+                || marker == self.separator
+                || self.symbol_conflicts(&marker)
+                || self.digits.keys().any(|digit| marker.contains(&digit[..])))
+        {
+            return Err(BibiError::BadNumeralSystem);
+        }
+        self.unary_group = marker;
+        Ok(())
     }
 
-    // compute  binary numbers into BCD like
-    fn shift_adjust(&self, mut pivot: Vec<bool>) -> Result<String, BibiError> {
-        let radix = self.numsys_out.len() as u32;
+    pub fn get_length_limit(&self) -> usize {
+        self.length_limit
+    }
 
-        let len_bits = pivot.len();
-        let mut bcdlike: Vec<u32> = vec![0];
+    /// Set the maximum number of marks written by a unary numeral system (2^20 by default)
+    pub fn set_length_limit(&mut self, length_limit: usize) {
+        self.length_limit = length_limit;
+    }
 
-        for _ in 0..len_bits {
-            // shift
-            let bit = pivot.remove(0);
-            let mut rel = if bit { 1 } else { 0 };
+    // the number is too long to be written in this unary numeral system
+    fn exceeds_length_limit(&self, limbs: &[u32]) -> bool {
+        if !self.is_unary() {
+            return false;
+        }
+        limbs.len() > 2 || limbs_to_u64(limbs) > self.length_limit as u64
+    }
 
-            // adjust
-            for idx in 0..bcdlike.len() {
-                let mut val = (bcdlike[idx] * 2) + rel;
-                rel = 0;
-                if val >= radix {
-                    val = val - radix;
-                    rel = 1;
-                }
-                bcdlike[idx] = val;
-            }
-            if rel == 1 {
-                bcdlike.push(rel);
+    // number of marks of an entry written in a unary system
+    fn read_unary(&self, entry: &str) -> Option<u64> {
+        let pieces: Vec<&str> = if self.unary_group.is_empty() {
+            vec![entry]
+        } else {
+            entry.split(&self.unary_group[..]).collect()
+        };
+        let mut count = 5 * (pieces.len() as u64 - 1);
+        for piece in pieces {
+            if !piece.is_empty() {
+                count += self.read_digits(piece)?.len() as u64;
             }
         }
+        Some(count)
+    }
 
-        let mut ret = self.numsys_out.prefix.clone();
-        for idx in (0..bcdlike.len()).rev() {
-            let val = bcdlike[idx];
-            ret = ret + self.numsys_out.revdigits.get(&val).unwrap();
+    // write a number in a unary system from its count given by shift_adjust
+    fn write_unary(&self, negative: bool, count: &[u32]) -> String {
+        let count = limbs_to_u64(count) as usize;
+        let (groups, marks) = if self.unary_group.is_empty() {
+            (0, count)
+        } else {
+            (count / 5, count % 5)
+        };
+        let mut parts: Vec<&str> = vec![&self.unary_group[..]; groups];
+        parts.extend(vec![&self.revdigits[&0][..]; marks]);
+        let mut ret = String::new();
+        if negative {
+            ret += self.minus_sign();
         }
+        ret + &self.prefix + &parts.join(&self.separator[..])
+    }
 
-        Ok(ret)
+    pub fn is_mixed_radix(&self) -> bool {
+        matches!(self.numeration, Numeration::Mixed { .. })
     }
-}
 
-#[cfg(test)]
-mod tests {
+    // digits of each position of a mixed-radix system (least significant first) and its head, none for
+    // other numeral systems
+    fn mixed_positions(&self) -> (&[NumeralSystem], Option<&NumeralSystem>) {
+        match self.numeration {
+            Numeration::Mixed {
+                ref positions,
+                ref head,
+            } => (positions, head.as_deref()),
+            _ => (&[], None),
+        }
+    }
+
+    pub fn is_negative_base(&self) -> bool {
+        self.numeration == Numeration::NegativeBase
+    }
+
+    /// Use the negative radix -len (-2 for negabinary, -10 for negadecimal) : every integer, negative or not, is written without sign. Numeral systems with a negative radix have no radix point, the default one coming back with standard numeration.
+    pub fn set_negative_base(&mut self, negative_base: bool) -> Result<(), BibiError> {
+        self.switch_numeration(Numeration::NegativeBase, negative_base)
+    }
+
+    pub fn get_values(&self) -> Vec<i32> {
+        self.digit_values().0.to_vec()
+    }
+
+    // values of the digits and digit written for each residue modulo the radix, none unless values are
+    // given
+    fn digit_values(&self) -> (&[i32], &[usize]) {
+        match self.numeration {
+            Numeration::Values {
+                ref values,
+                ref residues,
+            } => (values, residues),
+            _ => (&[], &[]),
+        }
+    }
+
+    /// Give explicit values to the digits, in the order of the digits, the radix being given apart : balanced ternary is written with values -1, 0, 1 in radix 3. Every residue modulo the radix must be the value of a digit (the radix is at most the number of digits), and every integer must be writable (as a natural number if no value is negative). When some values are negative, negative numbers are written without sign. Values must be smaller than radix² in absolute value. Numeral systems with digit values have no radix point, empty values coming back to 0..len-1 and to the default radix point.
+    pub fn set_values(&mut self, values: Vec<i32>, radix: usize) -> Result<(), BibiError> {
+        if values.is_empty() {
+            return self.switch_numeration(
+                Numeration::Values {
+                    values,
+                    residues: vec![],
+                },
+                false,
+            );
+        }
+        if values.len() != self.len()
+            || radix < 2
+            || radix > values.len()
+            || values.iter().any(|value| {
+                value.unsigned_abs() as u64 >= (radix as u64).saturating_mul(radix as u64)
+            })
+        {
+            return Err(BibiError::BadNumeralSystem);
+        }
+        let unique: HashSet<i32> = values.iter().cloned().collect();
+        if unique.len() != values.len() {
+            return Err(BibiError::BadNumeralSystem);
+        }
+        // the digit of smallest value (in absolute) for each residue
+        let mut residues: Vec<Option<usize>> = vec![None; radix];
+        for (idx, value) in values.iter().enumerate() {
+            let residue = &mut residues[(*value as i64).rem_euclid(radix as i64) as usize];
+            if residue.is_none_or(|other| values[other].unsigned_abs() > value.unsigned_abs()) {
+                *residue = Some(idx);
+            }
+        }
+        if residues.iter().any(|residue| residue.is_none()) {
+            return Err(BibiError::BadNumeralSystem);
+        }
+        let residues: Vec<usize> = residues.into_iter().flatten().collect();
+
+        // writing a digit of value v takes n to (n - v) / radix : with |v| <= max, numbers above
+        // max / (radix - 1) in absolute value get smaller and numbers of the window stay in it, so that
+        // every number ends in the window. Check that numbers of the window can be written.
+        let max = residues
+            .iter()
+            .map(|idx| values[*idx].unsigned_abs() as i64)
+            .max()
+            .unwrap_or(0);
+        let window = max / (radix as i64 - 1);
+        let start = if values.iter().any(|value| *value < 0) {
+            -window
+        } else {
+            0
+        };
+        let mut writable: HashSet<i64> = HashSet::new();
+        writable.insert(0);
+        for number in start..=window {
+            let mut seen = vec![];
+            let mut rest = number;
+            while !writable.contains(&rest) {
+                if seen.contains(&rest) {
+                    return Err(BibiError::BadNumeralSystem);
+                }
+                seen.push(rest);
+                let value = values[residues[rest.rem_euclid(radix as i64) as usize]] as i64;
+                rest = (rest - value) / radix as i64;
+            }
+            writable.extend(seen);
+        }
+
+        self.switch_numeration(Numeration::Values { values, residues }, true)
+    }
+
+    // negative numbers can be written, with a minus sign, a negative radix or negative digits
+    fn writes_negative(&self) -> bool {
+        !self.minus.is_empty() || self.is_negative_base() || self.has_negative_values()
+    }
+
+    fn has_negative_values(&self) -> bool {
+        self.digit_values().0.iter().any(|value| *value < 0)
+    }
+
+    pub fn get_fraction_bar(&self) -> String {
+        self.fraction_bar.clone()
+    }
+
+    /// Set the fraction bar written between the numerator and the denominator of a rational number (p/q). The fraction bar can not be part of a digit or be the separator or the radix point. An empty fraction bar means that rational numbers can not be read.
+    pub fn set_fraction_bar(&mut self, fraction_bar: &str) -> Result<(), BibiError> {
+        let fraction_bar: String = fraction_bar.nfc().collect();
+        if !fraction_bar.is_empty()
+            && (fraction_bar == self.separator
+                || fraction_bar == self.radix_point
+                || self.symbol_conflicts(&fraction_bar))
+        {
+            return Err(BibiError::BadNumeralSystem);
+        }
+        self.fraction_bar = fraction_bar;
+        Ok(())
+    }
+
+    pub fn get_separator(&self) -> String {
+        self.separator.clone()
+    }
+
+    /// Set the separator written between digits. The separator can not be part of a digit. If the separator is removed, digits must be readable without it.
+    pub fn set_separator(&mut self, separator: &str) -> Result<(), BibiError> {
+        // positions of mixed-radix systems are built for one separator
+        if self.is_mixed_radix() && separator != self.separator {
+            return Err(BibiError::BadNumeralSystem);
+        }
+        if !separator.is_empty()
+            && (separator == self.radix_point
+                || separator == self.fraction_bar
+                || self.symbol_conflicts(separator))
+        {
+            return Err(BibiError::BadNumeralSystem);
+        }
+        let previous = std::mem::replace(&mut self.separator, String::from(separator));
+        if let Err(err) = self.build_trie() {
+            self.separator = previous;
+            self.build_trie()?;
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    /// Encode bytes into this numeral system. Each leading zero byte gives one leading zero digit as in bitcoin base58 (or 8, 4, 2 digits for radices 2, 4, 16).
+    ///
+    /// Returns BibiError::Overflow if the number is longer than the length limit of a unary numeral system.
+    pub fn encode_bytes(&self, bytes: &[u8]) -> Result<String, BibiError> {
+        let zero_bytes = bytes.iter().take_while(|byte| **byte == 0).count();
+        let limbs = limbs_from_digits(bytes.iter().map(|byte| *byte as u32), 256);
+        if self.exceeds_length_limit(&limbs) {
+            return Err(BibiError::Overflow);
+        }
+        let is_zero = zero_bytes == bytes.len();
+        let mut bcdlike = self.shift_adjust(&limbs);
+        self.align_bytes(&mut bcdlike, is_zero, zero_bytes);
+        Ok(self.write_digits(false, &bcdlike))
+    }
+
+    /// Decode bytes encoded in this numeral system, leading zero digits giving zero bytes.
+    pub fn decode_bytes(&self, entry: &str) -> Result<Vec<u8>, BibiError> {
+        if entry.is_empty() {
+            return Ok(vec![]);
+        }
+        let pivot = self.integer_pivot(entry)?;
+        if pivot.negative && !pivot.is_zero() {
+            return Err(BibiError::EntryMismatchWithNumeralSystem);
+        }
+        let mut bytes = vec![0; pivot.zero_bytes];
+        let digits = limbs_to_digits(&pivot.limbs, 256);
+        bytes.extend(digits.iter().rev().map(|digit| *digit as u8));
+        Ok(bytes)
+    }
+
+    /// Write a native integer in this numeral system. Returns BibiError::Overflow if the number is longer than the length limit of a unary numeral system.
+    pub fn format_u128(&self, number: u128) -> Result<String, BibiError> {
+        self.write_integer(false, &Pivot::from_u128(number, false).limbs)
+    }
+
+    /// Read a number written in this numeral system into a native integer.
+    pub fn parse_u128(&self, entry: &str) -> Result<u128, BibiError> {
+        let pivot = self.integer_pivot(entry)?;
+        if pivot.negative && !pivot.is_zero() {
+            return Err(BibiError::Overflow);
+        }
+        pivot.to_u128()
+    }
+
+    /// Write a native signed integer in this numeral system. Negative numbers are written with the first minus sign, or give BibiError::NegativeNotSupported if the numeral system can not write them. Returns BibiError::Overflow if the number is longer than the length limit of a unary numeral system.
+    pub fn format_i128(&self, number: i128) -> Result<String, BibiError> {
+        let pivot = Pivot::from_u128(number.unsigned_abs(), number < 0);
+        self.write_integer(number < 0, &pivot.limbs)
+    }
+
+    /// Read a number written in this numeral system into a native signed integer.
+    pub fn parse_i128(&self, entry: &str) -> Result<i128, BibiError> {
+        let pivot = self.integer_pivot(entry)?;
+        let number = pivot.to_u128()?;
+        if pivot.negative {
+            if number > i128::MAX as u128 + 1 {
+                return Err(BibiError::Overflow);
+            }
+            Ok((number as i128).wrapping_neg())
+        } else {
+            i128::try_from(number).map_err(|_| BibiError::Overflow)
+        }
+    }
+
+    /// Write a big integer in this numeral system. Returns BibiError::Overflow if the number is longer than the length limit of a unary numeral system.
+    #[cfg(feature = "bigint")]
+    pub fn format_biguint(&self, number: &BigUint) -> Result<String, BibiError> {
+        self.write_integer(false, &number.to_u32_digits())
+    }
+
+    /// Read a number written in this numeral system into a big integer.
+    #[cfg(feature = "bigint")]
+    pub fn parse_biguint(&self, entry: &str) -> Result<BigUint, BibiError> {
+        let pivot = self.integer_pivot(entry)?;
+        if pivot.negative && !pivot.is_zero() {
+            return Err(BibiError::Overflow);
+        }
+        Ok(BigUint::new(pivot.limbs))
+    }
+
+    // write an integer given by its limbs, unless it is negative and can not be written so, or too long
+    // for a unary numeral system
+    fn write_integer(&self, negative: bool, limbs: &[u32]) -> Result<String, BibiError> {
+        if negative && !limbs.is_empty() && !self.writes_negative() {
+            return Err(BibiError::NegativeNotSupported);
+        }
+        if self.exceeds_length_limit(limbs) {
+            return Err(BibiError::Overflow);
+        }
+        Ok(self.write_digits(negative, &self.shift_adjust(limbs)))
+    }
+
+    // pivot of an entry which must not have a fractional part
+    fn integer_pivot(&self, entry: &str) -> Result<Pivot, BibiError> {
+        let pivot = self.tsujda_tfihs(entry)?;
+        if !pivot.is_integer() {
+            return Err(BibiError::FractionNotSupported);
+        }
+        Ok(pivot)
+    }
+
+    // read an integer fitting in a u128 with native arithmetic, without allocation : sign, value, number
+    // of leading zero digits and number of digits. None if the entry must be read by tsujda_tfihs
+    // (fractional part, overflow, bad digit...).
+    fn read_u128(&self, entry: &str) -> Option<(bool, u128, usize, usize)> {
+        if !self.streams(true) || !(entry.is_ascii() || is_nfc(entry)) {
+            return None;
+        }
+        let (sign, entry) = self.strip_sign(entry);
+        let rel_entry = if !self.prefix.is_empty()
+            && entry.len() > self.prefix.len()
+            && entry.starts_with(&self.prefix[..])
+        {
+            &entry[self.prefix.len()..]
+        } else {
+            entry
+        };
+        if rel_entry.is_empty()
+            || (!self.radix_point.is_empty() && rel_entry.contains(&self.radix_point[..]))
+        {
+            return None;
+        }
+
+        let radix = self.positional_radix() as u128;
+        let mut number: u128 = 0;
+        let mut zero_digits = 0;
+        let mut len_digits = 0;
+        let mut push = |digit: u32| {
+            if number == 0 && digit == 0 {
+                zero_digits += 1;
+            }
+            len_digits += 1;
+            number = number.checked_mul(radix)?.checked_add(digit as u128)?;
+            Some(())
+        };
+        if self.separator.is_empty() {
+            // digits are prefix-free : they are read one by one
+            let mut node = 0;
+            let mut rest = rel_entry;
+            while !rest.is_empty() {
+                // an ascii character followed by another one is a grapheme
+                let bytes = rest.as_bytes();
+                let len = if bytes[..bytes.len().min(2)].is_ascii() {
+                    if node == 0 {
+                        if let Some(value) = self.trie.ascii_digit(bytes[0]) {
+                            push(value)?;
+                            rest = &rest[1..];
+                            continue;
+                        }
+                    }
+                    1
+                } else {
+                    rest.graphemes(true).next().unwrap().len()
+                };
+                node = *self.trie.nodes[node].children.get(&rest[..len])?;
+                rest = &rest[len..];
+                if let Some(value) = self.trie.nodes[node].value {
+                    push(value)?;
+                    node = 0;
+                }
+            }
+            if node != 0 {
+                return None;
+            }
+        } else {
+            for digit in rel_entry.split(&self.separator[..]) {
+                push(*self.digits.get(digit)?)?;
+            }
+        }
+        Some((sign == Some(true), number, zero_digits, len_digits))
+    }
+
+    // write an integer fitting in a u128 as write_pivot does, after zeros leading zero digits, with native
+    // arithmetic and without intermediate allocation. None if the number must be written by write_pivot.
+    fn write_u128(&self, negative: bool, number: u128, zeros: usize) -> Option<String> {
+        let negative = negative && number > 0;
+        if !self.streams(false) || (negative && !self.writes_negative()) {
+            return None;
+        }
+        // digits least significant first, at most 128 in binary
+        let radix = self.positional_radix() as u128;
+        let mut digits = [0u32; 128];
+        let mut len = 0;
+        let mut rest = number;
+        while rest > u64::MAX as u128 {
+            digits[len] = (rest % radix) as u32;
+            rest /= radix;
+            len += 1;
+        }
+        // 64 bits divisions are much faster
+        let radix = radix as u64;
+        let mut rest = rest as u64;
+        loop {
+            digits[len] = (rest % radix) as u32;
+            rest /= radix;
+            len += 1;
+            if rest == 0 {
+                break;
+            }
+        }
+
+        let mut ret = String::new();
+        if negative {
+            ret += self.minus_sign();
+        }
+        ret += &self.prefix;
+        for idx in (0..len + zeros).rev() {
+            let digit = if idx < len { digits[idx] } else { 0 };
+            // digits are stored in order of their values
+            ret += &self.revdigits[digit as usize];
+            if idx > 0 {
+                ret += &self.separator;
+            }
+        }
+        Some(ret)
+    }
+
+    // number of zero bytes given by the leading zero digits of an entry
+    fn zero_bytes(&self, zero_digits: usize) -> usize {
+        match self.digits_per_byte() {
+            Some(per_byte) => zero_digits / per_byte,
+            None => zero_digits,
+        }
+    }
+
+    // pad digits (least significant first) to whole bytes and add the leading zero bytes
+    fn align_bytes(&self, bcdlike: &mut Vec<u32>, is_zero: bool, zero_bytes: usize) {
+        if is_zero {
+            bcdlike.clear();
+        }
+        let per_byte = self.digits_per_byte().unwrap_or(1);
+        let padded = bcdlike.len().div_ceil(per_byte) * per_byte;
+        bcdlike.resize(padded + zero_bytes * per_byte, 0);
+    }
+
+    // compute BCD  numbers into binary
+    fn tsujda_tfihs(&self, entry: &str) -> Result<Pivot, BibiError> {
+        let entry: String = entry.nfc().collect();
+        let (sign, entry) = self.strip_sign(&entry);
+
+        // erase the prefix if present
+        let rel_entry: &str = if !self.prefix.is_empty()
+            && (entry.len() > self.prefix.len() || self.is_bijective() || self.is_unary())
+            && entry.starts_with(&self.prefix[..])
+        {
+            &entry[self.prefix.len()..]
+        } else {
+            entry
+        };
+
+        let mut bcd: Vec<u32> = vec![];
+
+        if self.is_unary() {
+            let count = self
+                .read_unary(rel_entry)
+                .ok_or(BibiError::EntryMismatchWithNumeralSystem)?;
+            return Ok(Pivot {
+                len_digits: count as usize,
+                ..Pivot::from_u128(count as u128, sign == Some(true))
+            });
+        }
+
+        if self.is_mixed_radix() {
+            let (limbs, zero_digits, len_digits) = self
+                .read_mixed(rel_entry)
+                .ok_or(BibiError::EntryMismatchWithNumeralSystem)?;
+            return Ok(Pivot {
+                limbs,
+                negative: sign == Some(true),
+                fraction: vec![],
+                fraction_radix: 0,
+                zero_digits,
+                len_digits,
+                zero_bytes: self.zero_bytes(zero_digits),
+            });
+        }
+
+        // compute bcd numbers from the entry
+
+        // zero is written without digits in bijective numeration
+        if rel_entry.is_empty() && !self.is_bijective() {
+            return Err(BibiError::EntryMismatchWithNumeralSystem);
+        }
+
+        // split integer and fractional parts
+        let mut fraction: Vec<u32> = vec![];
+        let mut rel_entry = rel_entry;
+        if !self.radix_point.is_empty() {
+            if let Some(idx) = rel_entry.find(&self.radix_point[..]) {
+                let frac_entry = &rel_entry[idx + self.radix_point.len()..];
+                if !frac_entry.is_empty() {
+                    match self.read_digits(frac_entry) {
+                        Some(digits) => fraction = digits,
+                        None => return Err(BibiError::EntryMismatchWithNumeralSystem),
+                    }
+                }
+                rel_entry = &rel_entry[..idx];
+                if rel_entry.is_empty() && fraction.is_empty() {
+                    return Err(BibiError::EntryMismatchWithNumeralSystem);
+                }
+            }
+        }
+
+        if !rel_entry.is_empty() {
+            match self.read_digits(rel_entry) {
+                Some(digits) => bcd.extend(digits),
+                None => return Err(BibiError::EntryMismatchWithNumeralSystem),
+            }
+        }
+        let len_digits = bcd.len();
+        if self.is_bijective() {
+            bcd = self.read_bijective(&bcd);
+        }
+        let mut negative = sign == Some(true);
+        let (zero_digits, pivot) = if matches!(
+            self.numeration,
+            Numeration::NegativeBase | Numeration::Values { .. }
+        ) {
+            let values = self.signed_values(&bcd);
+            let zero_digits = values.iter().take_while(|value| **value == 0).count();
+            let (minus, limbs) = self.signed_values_to_limbs(&values);
+            negative = negative != minus;
+            (zero_digits, limbs)
+        } else {
+            let zero_digits = bcd.iter().take_while(|digit| **digit == 0).count();
+            (zero_digits, self.digits_to_limbs(&bcd))
+        };
+
+        // trailing zeros do not change the fraction
+        while fraction.last() == Some(&0) {
+            fraction.pop();
+        }
+
+        Ok(Pivot {
+            limbs: pivot,
+            negative,
+            fraction,
+            fraction_radix: self.positional_radix() as u32,
+            zero_digits,
+            len_digits,
+            zero_bytes: self.zero_bytes(zero_digits),
+        })
+    }
+
+    // signed value of each digit (most significant first) of a number written with a negative radix or
+    // explicit digit values, the radix being positive
+    fn signed_values(&self, bcd: &[u32]) -> Vec<i64> {
+        let len = bcd.len();
+        let values = self.digit_values().0;
+        bcd.iter()
+            .enumerate()
+            .map(|(idx, digit)| {
+                if !values.is_empty() {
+                    values[*digit as usize] as i64
+                } else if (len - 1 - idx).is_multiple_of(2) {
+                    *digit as i64
+                } else {
+                    -(*digit as i64)
+                }
+            })
+            .collect()
+    }
+
+    // sign and binary number of signed digit values, most significant first : positive and negative
+    // values are added apart and then subtracted
+    fn signed_values_to_limbs(&self, values: &[i64]) -> (bool, Vec<u32>) {
+        let plus: Vec<u32> = values
+            .iter()
+            .map(|value| value.max(&0).unsigned_abs() as u32)
+            .collect();
+        let minus: Vec<u32> = values
+            .iter()
+            .map(|value| value.min(&0).unsigned_abs() as u32)
+            .collect();
+        let mut plus = self.digits_to_limbs(&plus);
+        let mut minus = self.digits_to_limbs(&minus);
+        let negative = limbs_cmp(&plus, &minus) == Ordering::Less;
+        if negative {
+            std::mem::swap(&mut plus, &mut minus);
+        }
+        limbs_sub(&mut plus, &minus);
+        (negative, plus)
+    }
+
+    // reverse shift adjust : binary number (limbs) of digits, most significant first
+    fn digits_to_limbs(&self, bcd: &[u32]) -> Vec<u32> {
+        if let Some(bits) = self.bit_group() {
+            return limbs_from_groups(bcd, bits);
+        }
+        let radix = self.positional_radix() as u32;
+        // huge numbers are split by divide and conquer
+        if bcd.len() >= huge::CONQUER_THRESHOLD * radix_power(radix).1 {
+            huge::from_digits(bcd, radix)
+        } else {
+            limbs_from_digits(bcd.iter().copied(), radix)
+        }
+    }
+
+    // compute  binary numbers (limbs) into BCD like, least significant digit first
+    fn shift_adjust(&self, limbs: &[u32]) -> Vec<u32> {
+        if self.is_mixed_radix() {
+            return self.shift_adjust_mixed(limbs);
+        }
+        // unary systems get the number of marks, callers checking the length limit
+        if self.is_unary() {
+            debug_assert!(!self.exceeds_length_limit(limbs));
+            return limbs.to_vec();
+        }
+        let radix = self.positional_radix() as u32;
+        // each digit is a group of bits for radices 2, 4, 8... and huge numbers are split by divide and
+        // conquer
+        let mut bcdlike = if let Some(bits) = self.bits_per_digit() {
+            limbs_to_groups(limbs, bits)
+        } else if limbs.len() >= huge::CONQUER_THRESHOLD {
+            huge::to_digits(limbs, radix)
+        } else {
+            limbs_to_digits(limbs, radix)
+        };
+        if bcdlike.is_empty() {
+            bcdlike.push(0);
+        }
+        bcdlike
+    }
+
+    // returns the sign of the number (true for minus) if any, and the number without its sign
+    fn strip_sign<'a>(&self, entry: &'a str) -> (Option<bool>, &'a str) {
+        for sign in self.minus.iter() {
+            if let Some(rest) = entry.strip_prefix(&sign[..]) {
+                return (Some(true), rest);
+            }
+        }
+        for sign in self.plus.iter() {
+            if let Some(rest) = entry.strip_prefix(&sign[..]) {
+                return (Some(false), rest);
+            }
+        }
+        (None, entry)
+    }
+
+    // a separator, radix point or fraction bar can not be part of a digit
+    fn symbol_conflicts(&self, symbol: &str) -> bool {
+        self.all_digits().iter().any(|digit| digit.contains(symbol))
+    }
+
+    // digits of the numeral system, with the ones of the positions and head of mixed-radix systems
+    fn all_digits(&self) -> Vec<&str> {
+        let mut ret: Vec<&str> = self.digits.keys().map(|digit| &digit[..]).collect();
+        let (positions, head) = self.mixed_positions();
+        for position in positions.iter() {
+            ret.extend(position.all_digits());
+        }
+        if let Some(head) = head {
+            ret.extend(head.all_digits());
+        }
+        ret
+    }
+
+    // digits of a position of a mixed-radix system
+    fn position(&self, idx: usize) -> &NumeralSystem {
+        let positions = self.mixed_positions().0;
+        &positions[idx.min(positions.len() - 1)]
+    }
+
+    // binary number, number of leading zero digits and number of digits of an entry written in a
+    // mixed-radix system. Digits are read from the end of the entry.
+    fn read_mixed(&self, entry: &str) -> Option<(Vec<u32>, usize, usize)> {
+        let (positions, head) = self.mixed_positions();
+        let len_positions = positions.len();
+        // digits of the positions, least significant first
+        let mut digits: Vec<u32> = vec![];
+        let mut head_limbs: Vec<u32> = vec![];
+        let mut rest: Vec<&str> = if self.separator.is_empty() {
+            if !NumeralSystem::char_authorized(entry) {
+                return None;
+            }
+            entry.graphemes(true).collect()
+        } else {
+            entry.split(&self.separator[..]).collect()
+        };
+        while !rest.is_empty() {
+            if digits.len() >= len_positions {
+                if let Some(head) = head {
+                    let head_pivot = head.integer_pivot(&rest.join(&self.separator[..])).ok()?;
+                    if head_pivot.negative {
+                        return None;
+                    }
+                    head_limbs = head_pivot.limbs;
+                    break;
+                }
+            }
+            let position = self.position(digits.len());
+            let end = rest.len();
+            let (len, digit) = if self.separator.is_empty() {
+                // longest digit at the end of the entry
+                let longest = position
+                    .digits
+                    .keys()
+                    .map(|digit| digit.graphemes(true).count())
+                    .max()
+                    .unwrap_or(0);
+                (1..=longest.min(end)).rev().find_map(|len| {
+                    position
+                        .digits
+                        .get(&rest[end - len..].concat())
+                        .map(|digit| (len, *digit))
+                })?
+            } else {
+                (1, *position.digits.get(rest[end - 1])?)
+            };
+            digits.push(digit);
+            rest.truncate(end - len);
+        }
+        if digits.is_empty() {
+            return None;
+        }
+
+        let zero_digits = if !head_limbs.is_empty() {
+            0
+        } else {
+            digits.iter().rev().take_while(|digit| **digit == 0).count()
+        };
+        let mut limbs = head_limbs;
+        for (idx, digit) in digits.iter().enumerate().rev() {
+            limbs_mul_small(&mut limbs, self.position(idx).positional_radix() as u32);
+            limbs_add_small(&mut limbs, *digit);
+        }
+        Some((limbs, zero_digits, digits.len()))
+    }
+
+    // digits of each position of a mixed-radix system, least significant first, followed by the digits of
+    // the head if any
+    fn shift_adjust_mixed(&self, limbs: &[u32]) -> Vec<u32> {
+        let (positions, head) = self.mixed_positions();
+        let mut limbs = limbs.to_vec();
+        let mut ret = vec![];
+        loop {
+            if ret.len() >= positions.len() {
+                if let Some(head) = head {
+                    ret.extend(head.shift_adjust(&limbs));
+                    break;
+                }
+            }
+            let radix = self.position(ret.len()).positional_radix() as u32;
+            ret.push(limbs_divmod_small(&mut limbs, radix));
+            if limbs.is_empty() {
+                break;
+            }
+        }
+        ret
+    }
+
+    // write a number in a mixed-radix system from the digits given by shift_adjust_mixed
+    fn write_mixed(&self, negative: bool, bcdlike: &[u32]) -> String {
+        let (positions, head) = self.mixed_positions();
+        let len_positions = positions.len();
+        let mut parts: Vec<String> = vec![];
+        let mut len = bcdlike.len();
+        if let Some(head) = head {
+            if len > len_positions {
+                parts.push(head.write_number(false, &bcdlike[len_positions..], &[]));
+                len = len_positions;
+            }
+        }
+        for idx in (0..len).rev() {
+            parts.push(self.position(idx).revdigits[&bcdlike[idx]].clone());
+        }
+        let mut ret = String::new();
+        if negative {
+            ret += self.minus_sign();
+        }
+        ret + &self.prefix + &parts.join(&self.separator[..])
+    }
+
+    // a sign and a digit (or the prefix) can not start one another : "01" in bin would make "011" ambiguous
+    fn sign_conflicts(&self, sign: &str) -> bool {
+        let begins = |symbol: &str| {
+            !symbol.is_empty() && (symbol.starts_with(sign) || sign.starts_with(symbol))
+        };
+        sign.is_empty()
+            || begins(&self.prefix)
+            || self.all_digits().iter().any(|digit| begins(digit))
+    }
+
+    // standard digits (most significant first) of a number written in bijective numeration
+    fn read_bijective(&self, digits: &[u32]) -> Vec<u32> {
+        let radix = self.positional_radix() as u32;
+        let mut ret: Vec<u32> = vec![];
+        let mut carry = 0;
+        for digit in digits.iter().rev() {
+            let val = digit + 1 + carry;
+            carry = val / radix;
+            ret.push(val % radix);
+        }
+        if carry > 0 {
+            ret.push(carry);
+        }
+        ret.reverse();
+        ret
+    }
+
+    // digits in bijective numeration of standard digits, least significant first. Leading zeros are dropped.
+    fn write_bijective(&self, bcdlike: &[u32]) -> Vec<u32> {
+        let radix = self.positional_radix() as u32;
+        let top = match bcdlike.iter().rposition(|digit| *digit > 0) {
+            Some(top) => top,
+            None => return vec![],
+        };
+        let mut ret = vec![];
+        let mut borrow = 0;
+        for (idx, digit) in bcdlike[..=top].iter().enumerate() {
+            if idx == top && *digit == borrow {
+                break;
+            }
+            let mut val = *digit as i64 - borrow as i64;
+            borrow = 0;
+            if val <= 0 {
+                val += radix as i64;
+                borrow = 1;
+            }
+            ret.push(val as u32 - 1);
+        }
+        ret
+    }
+
+    // digits in the negative radix of a number given by its sign and its standard digits, least significant
+    // first. Leading zeros are dropped.
+    fn write_negative_base(&self, negative: bool, bcdlike: &[u32]) -> Vec<u32> {
+        let radix = self.positional_radix() as i64;
+        let mut ret = vec![];
+        let mut carry: i64 = 0;
+        for (idx, digit) in bcdlike.iter().enumerate() {
+            // radix^idx is (-radix)^idx for even positions and -(-radix)^idx for odd ones
+            let mut val = *digit as i64;
+            if negative != (idx % 2 == 1) {
+                val = -val;
+            }
+            let total = val + carry;
+            let digit = total.rem_euclid(radix);
+            carry = -(total - digit) / radix;
+            ret.push(digit as u32);
+        }
+        while carry != 0 {
+            let digit = carry.rem_euclid(radix);
+            carry = -(carry - digit) / radix;
+            ret.push(digit as u32);
+        }
+        while ret.len() > 1 && ret.last() == Some(&0) {
+            ret.pop();
+        }
+        ret
+    }
+
+    // digits with explicit values of a number given by its sign and its standard digits, least significant
+    // first. The number is written as a natural number if there are no negative values.
+    fn write_values(&self, negative: bool, bcdlike: &[u32]) -> Vec<u32> {
+        let radix = self.positional_radix() as i64;
+        let (values, residues) = self.digit_values();
+        let sign = if negative && self.has_negative_values() {
+            -1
+        } else {
+            1
+        };
+        let mut ret = vec![];
+        let mut carry: i64 = 0;
+        let mut rest = 0;
+        if let Some(top) = bcdlike.iter().rposition(|digit| *digit > 0) {
+            for digit in bcdlike[..top].iter() {
+                let total = sign * *digit as i64 + carry;
+                let idx = residues[total.rem_euclid(radix) as usize];
+                carry = (total - values[idx] as i64) / radix;
+                ret.push(idx as u32);
+            }
+            rest = sign * bcdlike[top] as i64 + carry;
+        }
+        // ends as checked by set_values
+        while rest != 0 {
+            let idx = residues[rest.rem_euclid(radix) as usize];
+            rest = (rest - values[idx] as i64) / radix;
+            ret.push(idx as u32);
+        }
+        if ret.is_empty() {
+            if let Some(zero) = values.iter().position(|value| *value == 0) {
+                ret.push(zero as u32);
+            }
+        }
+        ret
+    }
+
+    // first minus sign : negative numbers are only written with a sign if writes_negative, which callers
+    // check beforehand
+    fn minus_sign(&self) -> &str {
+        debug_assert!(!self.minus.is_empty(), "no minus sign to write");
+        self.minus.first().map_or("", |sign| &sign[..])
+    }
+
+    // write the digits (least significant first) with sign, prefix and separators
+    fn write_digits(&self, negative: bool, bcdlike: &[u32]) -> String {
+        self.write_number(negative, bcdlike, &[])
+    }
+
+    // write the integer digits (least significant first) and the fractional digits (most significant first)
+    fn write_number(&self, negative: bool, bcdlike: &[u32], fraction: &[u32]) -> String {
+        let negative = negative
+            && bcdlike
+                .iter()
+                .chain(fraction.iter())
+                .any(|digit| *digit > 0);
+        if self.is_unary() {
+            return self.write_unary(negative, bcdlike);
+        }
+        let converted;
+        let (negative, bcdlike) = match self.numeration {
+            Numeration::Standard => (negative, bcdlike),
+            Numeration::Bijective => {
+                converted = self.write_bijective(bcdlike);
+                (negative, &converted[..])
+            }
+            Numeration::NegativeBase => {
+                converted = self.write_negative_base(negative, bcdlike);
+                (false, &converted[..])
+            }
+            Numeration::Values { .. } => {
+                converted = self.write_values(negative, bcdlike);
+                (negative && !self.has_negative_values(), &converted[..])
+            }
+            Numeration::Mixed { .. } => return self.write_mixed(negative, bcdlike),
+        };
+        let mut ret = String::new();
+        if negative {
+            ret += self.minus_sign();
+        }
+        ret += &self.prefix;
+        for idx in (0..bcdlike.len()).rev() {
+            let val = bcdlike[idx];
+            ret = ret + self.revdigits.get(&val).unwrap();
+            if idx > 0 {
+                ret += &self.separator;
+            }
+        }
+        if !fraction.is_empty() {
+            ret += &self.radix_point;
+            for (idx, val) in fraction.iter().enumerate() {
+                if idx > 0 {
+                    ret += &self.separator;
+                }
+                ret += self.revdigits.get(val).unwrap();
+            }
+        }
+        ret
+    }
+
+    // write a number whose fractional digits (most significant first) repeat from period_start, the
+    // repeating part being enclosed in the marker
+    fn write_repeating(
+        &self,
+        negative: bool,
+        bcdlike: &[u32],
+        fraction: &[u32],
+        period_start: Option<usize>,
+        marker: &(String, String),
+    ) -> String {
+        let period_start = match period_start {
+            Some(period_start) => period_start,
+            None => return self.write_number(negative, bcdlike, fraction),
+        };
+        // the repeating part is never null, so the sign is always written
+        let mut ret = String::new();
+        if negative {
+            ret += self.minus_sign();
+        }
+        ret += &self.write_number(false, bcdlike, &fraction[..period_start]);
+        ret += if period_start == 0 {
+            &self.radix_point
+        } else {
+            &self.separator
+        };
+        ret += &marker.0;
+        for (idx, val) in fraction[period_start..].iter().enumerate() {
+            if idx > 0 {
+                ret += &self.separator;
+            }
+            ret += self.revdigits.get(val).unwrap();
+        }
+        ret + &marker.1
+    }
+
+    // write a number read by any codec, leading zeros being padded and the fractional part rounded to
+    // precision digits, the number being written on at least width_digits digits. Also tells if the
+    // fractional part was not cut.
+    fn write_pivot(
+        &self,
+        pivot: &Pivot,
+        leading_zeros: LeadingZeros,
+        precision: usize,
+        rounding: Rounding,
+        width_digits: usize,
+    ) -> Result<(String, bool), BibiError> {
+        let is_zero = pivot.is_zero();
+        if pivot.negative && !(is_zero && pivot.is_integer()) && !self.writes_negative() {
+            return Err(BibiError::NegativeNotSupported);
+        }
+        if !pivot.is_integer() && self.radix_point.is_empty() {
+            return Err(BibiError::FractionNotSupported);
+        }
+        if self.exceeds_length_limit(&pivot.limbs) {
+            return Err(BibiError::Overflow);
+        }
+        let mut bcdlike = self.shift_adjust(&pivot.limbs);
+        let (mut fraction, remainder) =
+            self.fraction_digits(pivot.fraction.clone(), pivot.fraction_radix, precision);
+        let round_up = match (rounding, remainder) {
+            (Rounding::HalfEven, Some(Ordering::Greater)) => true,
+            (Rounding::HalfEven, Some(Ordering::Equal)) => {
+                let last = fraction.last().or_else(|| bcdlike.first());
+                last.is_some_and(|digit| digit % 2 == 1)
+            }
+            _ => false,
+        };
+        if round_up {
+            let radix = self.positional_radix() as u32;
+            fraction.reverse();
+            if increment_digits(&mut fraction, radix) && increment_digits(&mut bcdlike, radix) {
+                bcdlike.push(1);
+            }
+            fraction.reverse();
+        }
+        while fraction.last() == Some(&0) {
+            fraction.pop();
+        }
+
+        match leading_zeros {
+            LeadingZeros::Drop => {}
+            LeadingZeros::Preserve => {
+                // the last zero of a null entry is the number itself
+                let zeros = if is_zero {
+                    pivot.len_digits.saturating_sub(1)
+                } else {
+                    pivot.zero_digits
+                };
+                bcdlike.resize(bcdlike.len() + zeros, 0);
+            }
+            LeadingZeros::ByteAligned => {
+                self.align_bytes(&mut bcdlike, is_zero, pivot.zero_bytes);
+                if bcdlike.is_empty() {
+                    bcdlike.push(0);
+                }
+            }
+        }
+
+        if bcdlike.len() < width_digits {
+            bcdlike.resize(width_digits, 0);
+        }
+
+        let number = self.write_number(pivot.negative, &bcdlike, &fraction);
+        Ok((number, remainder.is_none()))
+    }
+
+    // compute at most precision fractional digits in this numeral system of a fraction given by its digits
+    // (most significant first) in another radix. Also compares the remainder with one half, None if there
+    // is no remainder.
+    fn fraction_digits(
+        &self,
+        mut fraction: Vec<u32>,
+        fraction_radix: u32,
+        precision: usize,
+    ) -> (Vec<u32>, Option<Ordering>) {
+        // integers have no fractional digit, whatever the numeral system (mixed-radix ones included)
+        if fraction.iter().all(|digit| *digit == 0) {
+            return (vec![], None);
+        }
+        // between radices 2, 4, 8... the bits are only regrouped
+        if let Some(bits) = self.bit_group() {
+            if fraction_radix >= 2 && fraction_radix.is_power_of_two() {
+                let fraction_bits = fraction_radix.trailing_zeros() as usize;
+                return regroup_fraction(&fraction, fraction_bits, bits, precision);
+            }
+        }
+        let radix = self.positional_radix() as u32;
+        let mut ret = vec![];
+        while ret.len() < precision && fraction.iter().any(|digit| *digit > 0) {
+            ret.push(mul_fraction(&mut fraction, fraction_radix, radix));
+        }
+        if !fraction.iter().any(|digit| *digit > 0) {
+            return (ret, None);
+        }
+        let half = mul_fraction(&mut fraction, fraction_radix, 2);
+        let cmp = if half == 0 {
+            Ordering::Less
+        } else if fraction.iter().any(|digit| *digit > 0) {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        };
+        (ret, Some(cmp))
+    }
+}
+
+// conversions between native integers and numeral systems, through u128 and i128
+macro_rules! native_conversions {
+    ($($format:ident, $parse:ident, $native:ty, $wide:ty, $format_wide:ident, $parse_wide:ident);*) => {
+        impl NumeralSystem {
+            $(
+                #[doc = concat!("Write a native ", stringify!($native), " in this numeral system.")]
+                pub fn $format(&self, number: $native) -> Result<String, BibiError> {
+                    self.$format_wide(number as $wide)
+                }
+
+                #[doc = concat!("Read a number written in this numeral system into a native ", stringify!($native), ".")]
+                pub fn $parse(&self, entry: &str) -> Result<$native, BibiError> {
+                    <$native>::try_from(self.$parse_wide(entry)?).map_err(|_| BibiError::Overflow)
+                }
+            )*
+        }
+    };
+}
+
+native_conversions!(
+    format_u8, parse_u8, u8, u128, format_u128, parse_u128;
+    format_u16, parse_u16, u16, u128, format_u128, parse_u128;
+    format_u32, parse_u32, u32, u128, format_u128, parse_u128;
+    format_u64, parse_u64, u64, u128, format_u128, parse_u128;
+    format_usize, parse_usize, usize, u128, format_u128, parse_u128;
+    format_i8, parse_i8, i8, i128, format_i128, parse_i128;
+    format_i16, parse_i16, i16, i128, format_i128, parse_i128;
+    format_i32, parse_i32, i32, i128, format_i128, parse_i128;
+    format_i64, parse_i64, i64, i128, format_i128, parse_i128;
+    format_isize, parse_isize, isize, i128, format_i128, parse_i128
+);
+
+impl fmt::Display for NumeralSystem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut disp = String::from("");
+        let mut sep = String::from("");
+        for digit in self.digits.keys() {
+            disp = disp + &sep + digit;
+            sep = String::from(", ");
+        }
+        write!(f, "{}", disp)
+    }
+}
+
+/// Reads and writes numbers : numeral systems, Roman numerals, words or any other representation of numbers. `BibiCoder` converts numbers from one codec to another through the pivot.
+pub trait Codec: fmt::Debug {
+    /// Read a number
+    fn to_pivot(&self, entry: &str) -> Result<Pivot, BibiError>;
+
+    /// Write a number. Numbers which can not be written give BibiError::NegativeNotSupported, BibiError::FractionNotSupported or BibiError::NotRepresentable.
+    #[allow(clippy::wrong_self_convention)]
+    fn from_pivot(&self, pivot: &Pivot) -> Result<String, BibiError>;
+
+    /// Positional numeral system of the codec, if any : `BibiCoder` then writes fractions with its precision and rounding, leading zeros, fixed-width bit patterns and repeating digits.
+    fn numeral_system(&self) -> Option<&NumeralSystem> {
+        None
+    }
+}
+
+impl Codec for NumeralSystem {
+    fn to_pivot(&self, entry: &str) -> Result<Pivot, BibiError> {
+        self.tsujda_tfihs(entry)
+    }
+
+    /// Write a number, the fractional part being truncated to 20 digits
+    fn from_pivot(&self, pivot: &Pivot) -> Result<String, BibiError> {
+        let (number, _) = self.write_pivot(
+            pivot,
+            LeadingZeros::Drop,
+            DEFAULT_PRECISION,
+            Rounding::Truncate,
+            0,
+        )?;
+        Ok(number)
+    }
+
+    fn numeral_system(&self) -> Option<&NumeralSystem> {
+        Some(self)
+    }
+}
+
+// shared codecs, as given by `Registry`
+impl<C: Codec + ?Sized> Codec for Rc<C> {
+    fn to_pivot(&self, entry: &str) -> Result<Pivot, BibiError> {
+        (**self).to_pivot(entry)
+    }
+
+    fn from_pivot(&self, pivot: &Pivot) -> Result<String, BibiError> {
+        (**self).from_pivot(pivot)
+    }
+
+    fn numeral_system(&self) -> Option<&NumeralSystem> {
+        (**self).numeral_system()
+    }
+}
+
+/// What to do with the zero digits at the beginning of the input number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LeadingZeros {
+    /// Leading zeros are dropped : 0001 in hex gives 1 in dec
+    #[default]
+    Drop,
+    /// The number of leading zero digits is carried to the output : 0001 in hex gives 0001 in dec
+    Preserve,
+    /// Leading zeros are read as zero bytes, as in bitcoin base58 : each leading zero byte gives one leading zero digit.
+    /// For radices 2, 4, 16 and 256, one byte is written with 8, 4, 2 or 1 digit(s) and output is padded to whole bytes.
+    ByteAligned,
+}
+
+/// Number read by a codec and written by another one : binary representation of the number with the information needed to write it back (sign, fractional part, leading zeros).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Pivot {
+    // integer part, as 32 bits limbs least significant first
+    limbs: Vec<u32>,
+    negative: bool,
+    // digits of the fractional part, most significant first
+    fraction: Vec<u32>,
+    fraction_radix: u32,
+    // number of zero digits at the beginning of the entry
+    zero_digits: usize,
+    // number of digits of the entry
+    len_digits: usize,
+    // number of zero bytes given by the leading zeros of the entry
+    zero_bytes: usize,
+}
+
+impl Pivot {
+    /// Pivot of a native integer, written with a minus sign if negative
+    pub fn from_u128(number: u128, negative: bool) -> Pivot {
+        let limbs: Vec<u32> = (0..4).map(|idx| (number >> (32 * idx)) as u32).collect();
+        Pivot::from_limbs(&limbs, negative)
+    }
+
+    /// Pivot of an integer given by its digits in any radix (from 2), most significant first
+    pub fn from_digits(digits: &[u32], radix: u32, negative: bool) -> Pivot {
+        assert!(radix >= 2, "radix must be at least 2");
+        Pivot::from_limbs(&limbs_from_digits(digits.iter().copied(), radix), negative)
+    }
+
+    fn from_limbs(limbs: &[u32], negative: bool) -> Pivot {
+        let mut limbs = limbs.to_vec();
+        limbs_trim(&mut limbs);
+        Pivot {
+            limbs,
+            negative,
+            ..Default::default()
+        }
+    }
+
+    /// Digits of the integer part in any radix (from 2), most significant first. Zero has no digit.
+    pub fn to_digits(&self, radix: u32) -> Vec<u32> {
+        assert!(radix >= 2, "radix must be at least 2");
+        let mut ret = limbs_to_digits(&self.limbs, radix);
+        ret.reverse();
+        ret
+    }
+
+    /// The number is negative (or written with a minus sign for zero)
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    /// The integer part is zero
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    /// The number has no fractional part
+    pub fn is_integer(&self) -> bool {
+        self.fraction.is_empty()
+    }
+
+    /// Value of the integer part, without its sign. Returns BibiError::Overflow if it does not fit.
+    pub fn to_u128(&self) -> Result<u128, BibiError> {
+        if self.limbs.len() > 4 {
+            return Err(BibiError::Overflow);
+        }
+        Ok(self
+            .limbs
+            .iter()
+            .rev()
+            .fold(0, |acc, limb| (acc << 32) | *limb as u128))
+    }
+
+    // bits of the number written on exactly width bits (most significant first), if it fits
+    fn fixed_width(&self, width: usize) -> Option<Vec<bool>> {
+        let len = self.limbs.len() * 32
+            - self
+                .limbs
+                .last()
+                .map_or(0, |limb| limb.leading_zeros() as usize);
+        if len > width {
+            return None;
+        }
+        Some(
+            (0..width)
+                .rev()
+                .map(|idx| idx < len && (self.limbs[idx / 32] >> (idx % 32)) & 1 == 1)
+                .collect(),
+        )
+    }
+}
+
+/// Fixed-width representations of signed numbers as bit patterns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignedEncoding {
+    /// Negative numbers are 2^bits - |n| : -10 on 8 bits is 11110110
+    TwosComplement,
+    /// Negative numbers have all their bits inverted : -10 on 8 bits is 11110101
+    OnesComplement,
+    /// The highest bit is the sign : -10 on 8 bits is 10001010
+    SignMagnitude,
+}
+
+impl SignedEncoding {
+    // bit pattern of a signed number given by its magnitude on width bits
+    fn encode(self, negative: bool, magnitude: Vec<bool>) -> Option<Vec<bool>> {
+        if magnitude.is_empty() {
+            return None;
+        }
+        let zero = !magnitude.iter().any(|bit| *bit);
+        if !negative || zero {
+            // the sign bit must be free
+            return if magnitude[0] { None } else { Some(magnitude) };
+        }
+        match self {
+            SignedEncoding::TwosComplement => {
+                let mut pattern = invert(magnitude);
+                increment(&mut pattern);
+                if pattern[0] {
+                    Some(pattern)
+                } else {
+                    None
+                }
+            }
+            SignedEncoding::OnesComplement => {
+                if magnitude[0] {
+                    None
+                } else {
+                    Some(invert(magnitude))
+                }
+            }
+            SignedEncoding::SignMagnitude => {
+                if magnitude[0] {
+                    None
+                } else {
+                    let mut pattern = magnitude;
+                    pattern[0] = true;
+                    Some(pattern)
+                }
+            }
+        }
+    }
+
+    // sign and magnitude of a bit pattern
+    fn decode(self, pattern: Vec<bool>) -> (bool, Vec<bool>) {
+        if pattern.is_empty() || !pattern[0] {
+            return (false, pattern);
+        }
+        match self {
+            SignedEncoding::TwosComplement => {
+                let mut magnitude = invert(pattern);
+                increment(&mut magnitude);
+                (true, magnitude)
+            }
+            SignedEncoding::OnesComplement => (true, invert(pattern)),
+            SignedEncoding::SignMagnitude => {
+                let mut magnitude = pattern;
+                magnitude[0] = false;
+                (true, magnitude)
+            }
+        }
+    }
+}
+
+/// Side of the conversion where signed numbers are fixed-width bit patterns (see `BibiCoder::set_signed`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BitPatterns {
+    /// Input numbers are read as bit patterns and written with their sign : 0xfffffff6 on 32 bits in two's complement gives -10
+    #[default]
+    Input,
+    /// Input numbers are written as bit patterns : -10 on 8 bits in two's complement gives 0b11110110
+    Output,
+}
+
+/// Rounding applied to the last fractional digit when a fraction is cut.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Rounding {
+    /// Remaining digits are dropped
+    #[default]
+    Truncate,
+    /// Round to nearest, ties to even digit
+    HalfEven,
+}
+
+// multiply a fraction (digits in radix, most significant first) by mul, returns the integer part
+fn mul_fraction(fraction: &mut [u32], radix: u32, mul: u32) -> u32 {
+    let mut carry: u64 = 0;
+    for digit in fraction.iter_mut().rev() {
+        let val = *digit as u64 * mul as u64 + carry;
+        *digit = (val % radix as u64) as u32;
+        carry = val / radix as u64;
+    }
+    carry as u32
+}
+
+// add one to digits (least significant first) in radix, returns the carry
+fn increment_digits(digits: &mut [u32], radix: u32) -> bool {
+    for digit in digits.iter_mut() {
+        *digit += 1;
+        if *digit < radix {
+            return false;
+        }
+        *digit = 0;
+    }
+    true
+}
+
+// at most precision digits of bits bits of a fraction given by its digits of fraction_bits bits (most
+// significant first) : the bits are regrouped, and the remainder compared with one half as
+// fraction_digits does
+fn regroup_fraction(
+    fraction: &[u32],
+    fraction_bits: usize,
+    bits: usize,
+    precision: usize,
+) -> (Vec<u32>, Option<Ordering>) {
+    let mut ret = vec![];
+    // bits not yet written, and their number
+    let mut pending: u64 = 0;
+    let mut len = 0;
+    for digit in fraction {
+        pending = (pending << fraction_bits) | *digit as u64;
+        len += fraction_bits;
+        while len >= bits {
+            len -= bits;
+            ret.push((pending >> len) as u32);
+            pending &= (1 << len) - 1;
+        }
+    }
+    if len > 0 {
+        ret.push((pending << (bits - len)) as u32);
+    }
+    while ret.last() == Some(&0) {
+        ret.pop();
+    }
+    if ret.len() <= precision {
+        return (ret, None);
+    }
+    // the remainder ends with a digit which is not zero
+    let rest = ret.split_off(precision);
+    let cmp = match rest[0].cmp(&(1 << (bits - 1))) {
+        Ordering::Equal if rest.len() > 1 => Ordering::Greater,
+        cmp => cmp,
+    };
+    (ret, Some(cmp))
+}
+
+// arithmetic on natural numbers written as 32 bits limbs, least significant first, without null limbs at the end
+
+fn limbs_from_bits(bits: &[bool]) -> Vec<u32> {
+    let mut limbs = vec![0; bits.len().div_ceil(32)];
+    for (idx, bit) in bits.iter().rev().enumerate() {
+        if *bit {
+            limbs[idx / 32] |= 1 << (idx % 32);
+        }
+    }
+    limbs_trim(&mut limbs);
+    limbs
+}
+
+// number given by its digits in radix, most significant first : digits are gathered in chunks as
+// long as a power of the radix fits in a limb, each chunk costing one multiplication of the limbs
+fn limbs_from_digits<I: ExactSizeIterator<Item = u32>>(digits: I, radix: u32) -> Vec<u32> {
+    let (power, len) = radix_power(radix);
+    let mut limbs: Vec<u32> = vec![];
+    // the first chunk is shorter so that the others have len digits
+    let mut chunk_len = digits.len() % len;
+    if chunk_len == 0 {
+        chunk_len = len;
+    }
+    let mut chunk: u64 = 0;
+    for digit in digits {
+        chunk = chunk * radix as u64 + digit as u64;
+        chunk_len -= 1;
+        if chunk_len == 0 {
+            limbs_mul_small(&mut limbs, power);
+            limbs_add(&mut limbs, &[chunk as u32, (chunk >> 32) as u32]);
+            limbs_trim(&mut limbs);
+            chunk = 0;
+            chunk_len = len;
+        }
+    }
+    limbs
+}
+
+// digits of a number in radix, least significant first (zero has no digit) : the limbs are divided
+// by the greatest power of the radix fitting in a limb, giving a chunk of digits each time
+fn limbs_to_digits(limbs: &[u32], radix: u32) -> Vec<u32> {
+    let (power, len) = radix_power(radix);
+    let mut limbs = limbs.to_vec();
+    let mut ret = vec![];
+    while !limbs.is_empty() {
+        let mut chunk = limbs_divmod_small(&mut limbs, power);
+        // the last chunk has no leading zeros
+        for _ in 0..len {
+            if limbs.is_empty() && chunk == 0 {
+                break;
+            }
+            ret.push(chunk % radix);
+            chunk /= radix;
+        }
+    }
+    ret
+}
+
+// number given by its digits in radix 2^bits, most significant first : each digit is a group of bits
+// copied into the limbs
+fn limbs_from_groups(digits: &[u32], bits: usize) -> Vec<u32> {
+    let mut limbs = vec![0; (digits.len() * bits).div_ceil(32)];
+    for (idx, digit) in digits.iter().rev().enumerate() {
+        let pos = idx * bits;
+        limbs[pos / 32] |= digit << (pos % 32);
+        if pos % 32 + bits > 32 {
+            limbs[pos / 32 + 1] |= digit >> (32 - pos % 32);
+        }
+    }
+    limbs_trim(&mut limbs);
+    limbs
+}
+
+// digits of a number in radix 2^bits, least significant first (zero has no digit) : each digit is a
+// group of bits of the limbs
+fn limbs_to_groups(limbs: &[u32], bits: usize) -> Vec<u32> {
+    let len = limbs.len() * 32 - limbs.last().map_or(0, |limb| limb.leading_zeros() as usize);
+    let mask = (1u64 << bits) - 1;
+    (0..len.div_ceil(bits))
+        .map(|idx| {
+            let pos = idx * bits;
+            let mut group = (limbs[pos / 32] >> (pos % 32)) as u64;
+            if let Some(next) = limbs.get(pos / 32 + 1) {
+                group |= (*next as u64) << (32 - pos % 32);
+            }
+            (group & mask) as u32
+        })
+        .collect()
+}
+
+// greatest power of the radix fitting in a limb, with its exponent
+fn radix_power(radix: u32) -> (u32, usize) {
+    let mut power = radix;
+    let mut len = 1;
+    while let Some(next) = power.checked_mul(radix) {
+        power = next;
+        len += 1;
+    }
+    (power, len)
+}
+
+// value of a number of at most two limbs
+fn limbs_to_u64(limbs: &[u32]) -> u64 {
+    limbs
+        .iter()
+        .take(2)
+        .rev()
+        .fold(0, |acc, limb| (acc << 32) | *limb as u64)
+}
+
+fn limbs_trim(limbs: &mut Vec<u32>) {
+    while limbs.last() == Some(&0) {
+        limbs.pop();
+    }
+}
+
+fn limbs_cmp(a: &[u32], b: &[u32]) -> Ordering {
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+// a -= b, a being greater or equal to b
+fn limbs_sub(a: &mut Vec<u32>, b: &[u32]) {
+    let mut borrow = false;
+    for (idx, limb) in a.iter_mut().enumerate() {
+        let (val, borrow1) = limb.overflowing_sub(*b.get(idx).unwrap_or(&0));
+        let (val, borrow2) = val.overflowing_sub(borrow as u32);
+        *limb = val;
+        borrow = borrow1 || borrow2;
+    }
+    limbs_trim(a);
+}
+
+fn limbs_mul_small(a: &mut Vec<u32>, mul: u32) {
+    let mut carry: u64 = 0;
+    for limb in a.iter_mut() {
+        let val = *limb as u64 * mul as u64 + carry;
+        *limb = val as u32;
+        carry = val >> 32;
+    }
+    if carry > 0 {
+        a.push(carry as u32);
+    }
+    limbs_trim(a);
+}
+
+fn limbs_add_small(a: &mut Vec<u32>, add: u32) {
+    let mut carry = add as u64;
+    for limb in a.iter_mut() {
+        if carry == 0 {
+            break;
+        }
+        let val = *limb as u64 + carry;
+        *limb = val as u32;
+        carry = val >> 32;
+    }
+    if carry > 0 {
+        a.push(carry as u32);
+    }
+}
+
+// a += b
+fn limbs_add(a: &mut Vec<u32>, b: &[u32]) {
+    if a.len() < b.len() {
+        a.resize(b.len(), 0);
+    }
+    let mut carry: u64 = 0;
+    for (idx, limb) in a.iter_mut().enumerate() {
+        let val = *limb as u64 + *b.get(idx).unwrap_or(&0) as u64 + carry;
+        *limb = val as u32;
+        carry = val >> 32;
+    }
+    if carry > 0 {
+        a.push(carry as u32);
+    }
+}
+
+fn limbs_mul(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut ret = vec![0; a.len() + b.len()];
+    for (idx, x) in a.iter().enumerate() {
+        let mut carry: u64 = 0;
+        for (limb, y) in ret[idx..idx + b.len()].iter_mut().zip(b) {
+            let val = *limb as u64 + *x as u64 * *y as u64 + carry;
+            *limb = val as u32;
+            carry = val >> 32;
+        }
+        ret[idx + b.len()] = carry as u32;
+    }
+    limbs_trim(&mut ret);
+    ret
+}
+
+// a /= div, returns the remainder
+fn limbs_divmod_small(a: &mut Vec<u32>, div: u32) -> u32 {
+    let mut rem: u64 = 0;
+    for limb in a.iter_mut().rev() {
+        let val = (rem << 32) | *limb as u64;
+        *limb = (val / div as u64) as u32;
+        rem = val % div as u64;
+    }
+    limbs_trim(a);
+    rem as u32
+}
+
+fn invert(bits: Vec<bool>) -> Vec<bool> {
+    bits.into_iter().map(|bit| !bit).collect()
+}
+
+// add one to the bits, the carry out of the highest bit is lost
+fn increment(bits: &mut [bool]) {
+    for bit in bits.iter_mut().rev() {
+        *bit = !*bit;
+        if *bit {
+            break;
+        }
+    }
+}
+
+/// Convert any number from one numeral system (or any codec) to the other.
+#[derive(Debug)]
+pub struct BibiCoder {
+    numsys_in: Box<dyn Codec>,
+    numsys_out: Box<dyn Codec>,
+    // bits of a digit of numsys_in and numsys_out, both radices being powers of two
+    regrouping: Option<(usize, usize)>,
+    leading_zeros: LeadingZeros,
+    signed: Option<(SignedEncoding, usize)>,
+    bit_patterns: BitPatterns,
+    precision: usize,
+    rounding: Rounding,
+    repeat_marker: (String, String),
+}
+
+// maximum number of fractional digits written for a rational number
+const MAX_RATIONAL_DIGITS: usize = 100_000;
+
+// default maximum number of fractional digits written
+const DEFAULT_PRECISION: usize = 20;
+
+impl BibiCoder {
+    /// Build a coder from numsys_in numeral system to numsys_out. Any codec can be given (`NumeralSystem`, `Roman`, `Words` or your own).
+    ///
+    /// When both radices are powers of two (bin, oct, hex, bibi...), numbers are converted by regrouping their bits, in linear time (see `get_regrouping`).
+    pub fn new<I, O>(numsys_in: I, numsys_out: O) -> BibiCoder
+    where
+        I: Codec + 'static,
+        O: Codec + 'static,
+    {
+        BibiCoder::from_codecs(Box::new(numsys_in), Box::new(numsys_out))
+    }
+
+    /// Build a coder from boxed codecs
+    pub fn from_codecs(numsys_in: Box<dyn Codec>, numsys_out: Box<dyn Codec>) -> BibiCoder {
+        let regrouping = match (numsys_in.numeral_system(), numsys_out.numeral_system()) {
+            (Some(num_in), Some(num_out)) => num_in.bit_group().zip(num_out.bit_group()),
+            _ => None,
+        };
+        BibiCoder {
+            numsys_in,
+            numsys_out,
+            regrouping,
+            leading_zeros: LeadingZeros::Drop,
+            signed: None,
+            bit_patterns: BitPatterns::Input,
+            precision: DEFAULT_PRECISION,
+            rounding: Rounding::Truncate,
+            repeat_marker: (String::from("("), String::from(")")),
+        }
+    }
+
+    /// Number of bits written by a digit of numsys_in and by a digit of numsys_out, if numbers are converted by regrouping bits : 4 and 1 from hex to bin
+    pub fn get_regrouping(&self) -> Option<(usize, usize)> {
+        self.regrouping
+    }
+
+    /// Set the marks written around the repeating digits of rational numbers ("(" and ")" by default : 0.1(6))
+    pub fn set_repeat_marker(&mut self, open: &str, close: &str) {
+        self.repeat_marker = (String::from(open), String::from(close));
+    }
+
+    pub fn get_repeat_marker(&self) -> (String, String) {
+        self.repeat_marker.clone()
+    }
+
+    /// Set the maximum number of fractional digits written (20 by default)
+    pub fn set_precision(&mut self, precision: usize) {
+        self.precision = precision;
+    }
+
+    pub fn get_precision(&self) -> usize {
+        self.precision
+    }
+
+    /// Choose how the last fractional digit is rounded when the fraction is cut (truncated by default)
+    pub fn set_rounding(&mut self, rounding: Rounding) {
+        self.rounding = rounding;
+    }
+
+    pub fn get_rounding(&self) -> Rounding {
+        self.rounding
+    }
+
+    /// Read or write signed numbers as fixed-width bit patterns (None by default), on the side given by `set_bit_patterns` :
+    /// - input numbers are read as bit patterns of the given width and written with their sign : 0xfffffff6 on 32 bits in two's complement gives -10
+    /// - or input numbers are written as bit patterns of the given width, padded for radices 2, 4, 8... : -10 on 8 bits in two's complement gives 0b11110110
+    ///
+    /// Numbers which do not fit in the width give BibiError::Overflow, bit patterns read with a minus sign give BibiError::NegativeNotSupported.
+    pub fn set_signed(&mut self, signed: Option<(SignedEncoding, usize)>) {
+        self.signed = signed;
+    }
+
+    pub fn get_signed(&self) -> Option<(SignedEncoding, usize)> {
+        self.signed
+    }
+
+    /// Choose whether input or output numbers are the bit patterns of `set_signed` (input by default)
+    pub fn set_bit_patterns(&mut self, bit_patterns: BitPatterns) {
+        self.bit_patterns = bit_patterns;
+    }
+
+    pub fn get_bit_patterns(&self) -> BitPatterns {
+        self.bit_patterns
+    }
+
+    /// Choose what to do with leading zeros of input numbers (dropped by default)
+    pub fn set_leading_zeros(&mut self, leading_zeros: LeadingZeros) {
+        self.leading_zeros = leading_zeros;
+    }
+
+    pub fn get_leading_zeros(&self) -> LeadingZeros {
+        self.leading_zeros
+    }
+
+    /// find all numbers in entry from regular expression
+    pub fn extract_numbers(entry: &str, reg: &str) -> Result<Vec<String>, BibiError> {
+        let mut numbers: Vec<String> = vec![];
+
+        if !reg.is_empty() {
+            let re: Regex = match Regex::new(reg) {
+                Ok(res) => res,
+                Err(_) => return Err(BibiError::BadRegularExpression),
+            };
+            let caps = match re.captures(entry) {
+                Some(caps) => caps,
+                None => return Err(BibiError::RegexMismatchWithEntry),
+            };
+            for numberok in caps.iter().skip(1).flatten() {
+                numbers.push(numberok.as_str().to_string());
+            }
+        } else {
+            numbers.push(entry.to_string());
+        }
+
+        Ok(numbers)
+    }
+
+    /// Swap a number coded in numsys_in system to numsys_out. The sign of the number, if any, is written with the first minus sign of numsys_out. The fractional part, if any, is written with at most precision digits.
+    pub fn swap(&self, entry: &str) -> Result<String, BibiError> {
+        Ok(self.swap_with_exactness(entry)?.0)
+    }
+
+    /// Swap a rational number p/q (numerator and denominator written in numsys_in, separated by its fraction bar) to its exact expansion in numsys_out. Repeating digits are enclosed in the repeat marker : 1/6 gives 0.1(6) in decimal. Expansions longer than 100000 digits give BibiError::Overflow.
+    ///
+    /// Codecs which are not numeral systems use "/" as fraction bar, and only write integers.
+    pub fn swap_rational(&self, entry: &str) -> Result<String, BibiError> {
+        let entry: String = entry.nfc().collect();
+        let bar = self
+            .numsys_in
+            .numeral_system()
+            .map_or(String::from("/"), |num| num.fraction_bar.clone());
+        let idx = match entry.find(&bar[..]) {
+            Some(idx) if !bar.is_empty() => idx,
+            _ => return Err(BibiError::EntryMismatchWithNumeralSystem),
+        };
+        let numerator = self.numsys_in.to_pivot(&entry[..idx])?;
+        let denominator = self.numsys_in.to_pivot(&entry[idx + bar.len()..])?;
+        if !numerator.is_integer() || !denominator.is_integer() {
+            return Err(BibiError::FractionNotSupported);
+        }
+        if denominator.is_zero() {
+            return Err(BibiError::DivisionByZero);
+        }
+        let negative = numerator.negative != denominator.negative && !numerator.is_zero();
+        let denominator = denominator.limbs;
+        let (quotient, mut rem) = huge::divmod(&numerator.limbs, &denominator);
+        let numsys_out = match self.numsys_out.numeral_system() {
+            Some(numsys_out) => numsys_out,
+            None if rem.is_empty() => {
+                return self
+                    .numsys_out
+                    .from_pivot(&Pivot::from_limbs(&quotient, negative))
+            }
+            None => return Err(BibiError::FractionNotSupported),
+        };
+        if negative && !numsys_out.writes_negative() {
+            return Err(BibiError::NegativeNotSupported);
+        }
+
+        if !rem.is_empty() && numsys_out.radix_point.is_empty() {
+            return Err(BibiError::FractionNotSupported);
+        }
+
+        // long division, stopped when a remainder comes back
+        let mut fraction: Vec<u32> = vec![];
+        let mut remainders: HashMap<Vec<u32>, usize> = HashMap::new();
+        let mut period_start = None;
+        while !rem.is_empty() {
+            if let Some(pos) = remainders.get(&rem) {
+                period_start = Some(*pos);
+                break;
+            }
+            if fraction.len() >= MAX_RATIONAL_DIGITS {
+                return Err(BibiError::Overflow);
+            }
+            remainders.insert(rem.clone(), fraction.len());
+            limbs_mul_small(&mut rem, numsys_out.positional_radix() as u32);
+            let (digit, next) = huge::divmod(&rem, &denominator);
+            fraction.push(digit.first().copied().unwrap_or(0));
+            rem = next;
+        }
+
+        if numsys_out.exceeds_length_limit(&quotient) {
+            return Err(BibiError::Overflow);
+        }
+        let bcdlike = numsys_out.shift_adjust(&quotient);
+        Ok(numsys_out.write_repeating(
+            negative,
+            &bcdlike,
+            &fraction,
+            period_start,
+            &self.repeat_marker,
+        ))
+    }
+
+    /// Numbers can be converted from a stream (see `swap_stream`) : numeral systems of the same radix (digits are transliterated) or of radices which are powers of two (see `get_regrouping`), without bijective numeration, negative radix or digit values. Input digits must be delimited by a separator or prefix-free (no digit is the beginning of another one). Fixed-width bit patterns, byte-aligned leading zeros and rounding are not streamed (fractional parts are truncated).
+    pub fn can_stream(&self) -> bool {
+        let (num_in, num_out) = match (
+            self.numsys_in.numeral_system(),
+            self.numsys_out.numeral_system(),
+        ) {
+            (Some(num_in), Some(num_out)) => (num_in, num_out),
+            _ => return false,
+        };
+        self.signed.is_none()
+            && self.leading_zeros != LeadingZeros::ByteAligned
+            && self.rounding == Rounding::Truncate
+            && num_in.streams(true)
+            && num_out.streams(false)
+            && (num_in.radix() == num_out.radix() || self.regrouping.is_some())
+    }
+
+    /// Swap a number read from input and write it to output, digit by digit : multi-gigabyte hex dumps can be written in bibi without holding them in memory. The number is written as `swap` writes it, whitespace of the input (line breaks) being ignored.
+    ///
+    /// Memory is bounded only for the same radix or when an input digit is made of whole output digits (hex to bibi, hex to bin). Otherwise (hex to base32, bin to hex) the first output digit depends on the length of the integer part : the bits of the integer part are kept until its end, taking half the size of a hex dump in memory.
+    ///
+    /// Gives BibiError::NotStreamable unless `can_stream`, and BibiError::BadFile if input or output fails. The output is written as the input is read : on error, the beginning of the number may already be written.
+    ///
+    /// ## Exemple
+    ///
+    ///        let hex = bibicode::NumeralSystem::new_from_tag("hex").unwrap();
+    ///        let bibi = bibicode::NumeralSystem::new_from_tag("bibi").unwrap();
+    ///        let coder = bibicode::BibiCoder::new(hex, bibi);
+    ///        let mut output = vec![];
+    ///        coder.swap_stream("0x7d0\n".as_bytes(), &mut output).unwrap();
+    ///        assert_eq!(String::from_utf8(output).unwrap(), "BIDAHO");
+    pub fn swap_stream<R: Read, W: Write>(&self, input: R, output: W) -> Result<(), BibiError> {
+        if !self.can_stream() {
+            return Err(BibiError::NotStreamable);
+        }
+        let num_in = self.numsys_in.numeral_system().unwrap();
+        let num_out = self.numsys_out.numeral_system().unwrap();
+        stream::swap(
+            input,
+            output,
+            num_in,
+            num_out,
+            self.leading_zeros,
+            self.precision,
+        )
+    }
+
+    /// Swap a number as `swap` does, and tell if the result is exact, ie the fractional part was not cut to precision digits.
+    pub fn swap_with_exactness(&self, entry: &str) -> Result<(String, bool), BibiError> {
+        if let Some(number) = self.swap_u128(entry) {
+            return Ok((number, true));
+        }
+        let mut pivot = self.numsys_in.to_pivot(entry)?;
+        let numsys_out = self.numsys_out.numeral_system();
+        if !pivot.is_integer()
+            && (self.signed.is_some() || numsys_out.is_some_and(|num| num.radix_point.is_empty()))
+        {
+            return Err(BibiError::FractionNotSupported);
+        }
+        // digits needed to write the whole bit pattern
+        let mut width_digits = 0;
+        if let Some((encoding, width)) = self.signed {
+            let fixed = pivot.fixed_width(width).ok_or(BibiError::Overflow)?;
+            if self.bit_patterns == BitPatterns::Output {
+                let pattern = encoding
+                    .encode(pivot.negative, fixed)
+                    .ok_or(BibiError::Overflow)?;
+                pivot.limbs = limbs_from_bits(&pattern);
+                pivot.negative = false;
+                if let Some(per_digit) = numsys_out.and_then(|num| num.bits_per_digit()) {
+                    width_digits = width.div_ceil(per_digit);
+                }
+            } else {
+                if pivot.negative && !pivot.limbs.is_empty() {
+                    return Err(BibiError::NegativeNotSupported);
+                }
+                let (negative, magnitude) = encoding.decode(fixed);
+                pivot.limbs = limbs_from_bits(&magnitude);
+                pivot.negative = negative;
+            }
+        }
+        match numsys_out {
+            Some(numsys_out) => numsys_out.write_pivot(
+                &pivot,
+                self.leading_zeros,
+                self.precision,
+                self.rounding,
+                width_digits,
+            ),
+            None => Ok((self.numsys_out.from_pivot(&pivot)?, true)),
+        }
+    }
+
+    // integers fitting in a u128 are converted with native arithmetic, without intermediate allocation.
+    // Positional numeral systems only, big numbers going through the pivot.
+    fn swap_u128(&self, entry: &str) -> Option<String> {
+        if self.signed.is_some() || self.leading_zeros == LeadingZeros::ByteAligned {
+            return None;
+        }
+        let num_in = self.numsys_in.numeral_system()?;
+        let num_out = self.numsys_out.numeral_system()?;
+        let (negative, number, zero_digits, len_digits) = num_in.read_u128(entry)?;
+        let zeros = match self.leading_zeros {
+            // the last zero of a null entry is the number itself
+            LeadingZeros::Preserve if number == 0 => len_digits - 1,
+            LeadingZeros::Preserve => zero_digits,
+            _ => 0,
+        };
+        num_out.write_u128(negative, number, zeros)
+    }
+}
+
+#[cfg(test)]
+mod tests {
     use super::*;
 
     #[test]
-    fn test_numeral_system() {
-        let test = NumeralSystem::new("", vec![vec!["0", "1", "2", "2"]]);
-        assert!(test.is_err(), "test 1 1");
+    fn test_numeral_system() {
+        let test = NumeralSystem::new("", vec![vec!["0", "1", "2", "2"]]);
+        assert!(test.is_err(), "test 1 1");
+
+        let test = NumeralSystem::new("", vec![vec!["0", "1", "2", "22"]]);
+        assert!(test.is_err(), "test 1 2");
+
+        let test = NumeralSystem::new("", vec![vec!["0", "1", "2"], vec!["0", "1", "2", "22"]]);
+        assert!(test.is_err(), "test 1 3");
+
+        let test = NumeralSystem::new("", vec![vec!["0", "1", "2"], vec!["0", "1", "2", "2"]]);
+        assert!(test.is_err(), "test 1 4");
+
+        let a = vec![
+            "B", "K", "D", "F", "G", "J", "L", "M", "N", "P", "R", "S", "T", "V", "X", "Z",
+        ];
+        let b = vec!["a", "i", "o", "u"];
+        let len1 = a.len() * b.len();
+        let test = NumeralSystem::new("", vec![a, b]).unwrap();
+        let len2 = test.digits.len();
+        assert!(len1 == len2, "test 1 5");
+    }
+
+    #[test]
+    fn test_bibicoder() {
+        let dec = NumeralSystem::new(
+            "",
+            vec![vec!["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"]],
+        )
+        .unwrap();
+        let bibi = NumeralSystem::new(
+            "",
+            vec![vec![
+                "HO", "HA", "HE", "HI", "BO", "BA", "BE", "BI", "KO", "KA", "KE", "KI", "DO", "DA",
+                "DE", "DI",
+            ]],
+        )
+        .unwrap();
+
+        let dec_to_bibi = BibiCoder::new(dec, bibi);
+
+        let test = dec_to_bibi.swap("2000").unwrap();
+        assert_eq!(test, "BIDAHO", "test 2 1");
+
+        //let bibi = NumeralSystem::new("", "", vec!(vec!("H", "B", "K", "D"), vec!("O", "A", "E", "I"))).unwrap();
+        let test = dec_to_bibi.swap("2000").unwrap();
+        assert_eq!(test, "BIDAHO", "test 2 2");
+
+        let bin = NumeralSystem::new_from_tag("bin").unwrap();
+        let hex = NumeralSystem::new_from_tag("hex").unwrap();
+        let dec = NumeralSystem::new_from_tag("dec").unwrap();
+
+        let hex_to_dec = BibiCoder::new(hex, dec);
+
+        let dec = NumeralSystem::new_from_tag("dec").unwrap();
+        let hex = NumeralSystem::new_from_tag("hex").unwrap();
+        let dec_to_hex = BibiCoder::new(dec, hex);
+
+        let hex = NumeralSystem::new_from_tag("hex").unwrap();
+        let hex_to_bin = BibiCoder::new(hex, bin);
+
+        let test = hex_to_dec.swap("ffff").unwrap();
+        assert_eq!(test, "65535", "test 2 3");
+
+        let test = hex_to_bin.swap("f0ff").unwrap();
+        assert_eq!(test, "0b1111000011111111", "test 2 4");
+
+        let test = dec_to_hex
+            .swap("324439924324324235436544328757654635345424324543")
+            .unwrap();
+
+        assert_eq!(
+            test, "0x38d463ad8fa67a74d6e9a610158623c60d2297bf",
+            "test 2 5"
+        );
+
+        let test = dec_to_hex.swap("45641230").unwrap();
+        assert_eq!(test, "0x2b86e0e", "test 2 6");
+
+        let test = hex_to_dec
+            .swap("38d463ad8fa67a74d6e9a610158623c60d2297bf")
+            .unwrap();
+
+        assert_eq!(
+            test, "324439924324324235436544328757654635345424324543",
+            "test 2 7"
+        );
+
+        let sys = NumeralSystem::new(
+            "",
+            vec![
+                vec![
+                    "B", "K", "D", "F", "G", "J", "L", "M", "N", "P", "R", "S", "T", "V", "X", "Z",
+                ],
+                vec!["a-", "i-", "o-", "u-"],
+            ],
+        )
+        .unwrap();
+
+        let hex = NumeralSystem::new_from_tag("hex").unwrap();
+        let hex_to_sys = BibiCoder::new(hex, sys);
+        let test = hex_to_sys
+            .swap("de0b295669a9fd93d5f28d9ec85e40f4cb697bae")
+            .unwrap();
+
+        let test2 = hex_to_sys
+            .swap("0xde0b295669a9fd93d5f28d9ec85e40f4cb697bae")
+            .unwrap();
+
+        assert_eq!(
+            test,
+            "Fi-Xa-Du-Do-Ji-Li-Ri-Ro-Mu-Vo-Gu-Vi-Mu-Do-Fi-Pu-Sa-Ni-Mo-Ga-Fu-Gu-Du-Lo-Ju-So-So-",
+            "test 2 8"
+        );
+        assert_eq!(
+            test2,
+            "Fi-Xa-Du-Do-Ji-Li-Ri-Ro-Mu-Vo-Gu-Vi-Mu-Do-Fi-Pu-Sa-Ni-Mo-Ga-Fu-Gu-Du-Lo-Ju-So-So-",
+            "test 2 9"
+        );
+
+        let sys = NumeralSystem::new(
+            "",
+            vec![
+                vec![
+                    "B", "K", "D", "F", "G", "J", "L", "M", "N", "P", "R", "S", "T", "V", "X", "Z",
+                ],
+                vec!["a-", "i-", "o-", "u-"],
+            ],
+        )
+        .unwrap();
+        let hex = NumeralSystem::new_from_tag("hex").unwrap();
+        let sys_to_hex = BibiCoder::new(sys, hex);
+        let test = sys_to_hex
+            .swap(
+                "Fi-Xa-Du-Do-Ji-Li-Ri-Ro-Mu-Vo-Gu-Vi-Mu-Do-Fi-Pu-Sa-Ni-Mo-Ga-Fu-Gu-Du-Lo-Ju-So-So-",
+            )
+            .unwrap();
+
+        assert_eq!(
+            test, "0xde0b295669a9fd93d5f28d9ec85e40f4cb697bae",
+            "test 2 10"
+        );
+    }
+
+    #[test]
+    fn test_errorok() {
+        let dec = NumeralSystem::new_from_tag("dec").unwrap();
+        let bibi = NumeralSystem::new_from_tag("bibi").unwrap();
+        let bibi_to_dec: BibiCoder = BibiCoder::new(bibi, dec);
+
+        let test = bibi_to_dec.swap("0");
+        assert!(test.is_err());
+
+        let test = bibi_to_dec.swap("");
+        assert!(test.is_err());
+    }
+
+    #[test]
+    fn test_unicode() {
+        let greek = NumeralSystem::new("", vec![vec!["α", "β", "γ", "δ"]]).unwrap();
+        let dec = NumeralSystem::new_from_tag("dec").unwrap();
+        let dec_to_greek = BibiCoder::new(dec, greek);
+        assert_eq!(dec_to_greek.swap("27").unwrap(), "βγδ", "test 4 1");
+
+        // combining sequences are single graphemes, input is NFC-normalized
+        let accents = NumeralSystem::new("", vec![vec!["e\u{301}", "e\u{300}"]]).unwrap();
+        let dec = NumeralSystem::new_from_tag("dec").unwrap();
+        let accents_to_dec = BibiCoder::new(accents, dec);
+        assert_eq!(
+            accents_to_dec.swap("\u{e8}\u{e9}\u{e8}").unwrap(),
+            "5",
+            "test 4 2"
+        );
+        assert_eq!(
+            accents_to_dec.swap("e\u{300}e\u{301}e\u{300}").unwrap(),
+            "5",
+            "test 4 3"
+        );
+
+        let emoji = NumeralSystem::new("", vec![vec!["🙂", "👍🏽", "🇫🇷"]]).unwrap();
+        let dec = NumeralSystem::new_from_tag("dec").unwrap();
+        let emoji_to_dec = BibiCoder::new(emoji, dec);
+        assert_eq!(emoji_to_dec.swap("🇫🇷👍🏽🙂").unwrap(), "21", "test 4 4");
+
+        // must not panic on multi-bytes chars against ascii prefix
+        let hex = NumeralSystem::new_from_tag("hex").unwrap();
+        let dec = NumeralSystem::new_from_tag("dec").unwrap();
+        let hex_to_dec = BibiCoder::new(hex, dec);
+        assert!(hex_to_dec.swap("é").is_err(), "test 4 5");
+        assert!(hex_to_dec.swap("0é").is_err(), "test 4 6");
+
+        let test = NumeralSystem::new("", vec![vec!["a", "\u{301}"], vec!["\u{301}", "b"]]);
+        assert!(test.is_err(), "test 4 7");
+
+        let utf8 = NumeralSystem::new_from_tag("utf8").unwrap();
+        let dec = NumeralSystem::new_from_tag("dec").unwrap();
+        let dec_to_utf8 = BibiCoder::new(dec, utf8);
+        assert_eq!(dec_to_utf8.swap("33").unwrap(), "☰♣☰♣", "test 4 8");
+    }
+
+    #[test]
+    fn test_variable_width() {
+        let sys = NumeralSystem::new("", vec![vec!["0", "1", "10x", "z"]]).unwrap();
+        let dec = NumeralSystem::new_from_tag("dec").unwrap();
+        let dec_to_sys = BibiCoder::new(dec, sys);
+        assert_eq!(dec_to_sys.swap("39").unwrap(), "10x1z", "test 5 1");
+
+        let sys = NumeralSystem::new("", vec![vec!["0", "1", "10x", "z"]]).unwrap();
+        let dec = NumeralSystem::new_from_tag("dec").unwrap();
+        let sys_to_dec = BibiCoder::new(sys, dec);
+        assert_eq!(sys_to_dec.swap("10x1z").unwrap(), "39", "test 5 2");
+        assert_eq!(sys_to_dec.swap("110x").unwrap(), "6", "test 5 3");
+        assert!(sys_to_dec.swap("10").is_ok(), "test 5 4");
+        assert!(sys_to_dec.swap("1x").is_err(), "test 5 5");
+
+        // morse like code, prefix-free
+        let morse = NumeralSystem::new("", vec![vec![".", "-.", "--.", "---"]]).unwrap();
+        let dec = NumeralSystem::new_from_tag("dec").unwrap();
+        let morse_to_dec = BibiCoder::new(morse, dec);
+        assert_eq!(morse_to_dec.swap("----..").unwrap(), "52", "test 5 6");
+
+        // uniquely decodable but not prefix-free
+        let sys = NumeralSystem::new("", vec![vec!["a", "ab", "bb"]]).unwrap();
+        let dec = NumeralSystem::new_from_tag("dec").unwrap();
+        let sys_to_dec = BibiCoder::new(sys, dec);
+        assert_eq!(sys_to_dec.swap("abb").unwrap(), "2", "test 5 7");
+
+        match NumeralSystem::new("", vec![vec!["a", "ab", "b"]]) {
+            Err(BibiError::AmbiguousDigits(digits)) => {
+                assert_eq!(digits, vec!["a", "ab", "b"], "test 5 8")
+            }
+            _ => panic!("test 5 8"),
+        }
+    }
+
+    #[test]
+    fn test_separator() {
+        let budu = NumeralSystem::new_with_separator(
+            "",
+            "-",
+            vec![
+                vec![
+                    "B", "K", "D", "F", "G", "J", "L", "M", "N", "P", "R", "S", "T", "V", "X", "Z",
+                ],
+                vec!["a", "i", "o", "u"],
+            ],
+        )
+        .unwrap();
+        let hex = NumeralSystem::new_from_tag("hex").unwrap();
+        let hex_to_budu = BibiCoder::new(hex, budu.clone());
+        let test = hex_to_budu
+            .swap("de0b295669a9fd93d5f28d9ec85e40f4cb697bae")
+            .unwrap();
+        assert_eq!(
+            test,
+            "Fi-Xa-Du-Do-Ji-Li-Ri-Ro-Mu-Vo-Gu-Vi-Mu-Do-Fi-Pu-Sa-Ni-Mo-Ga-Fu-Gu-Du-Lo-Ju-So-So",
+            "test 6 1"
+        );
+
+        let hex = NumeralSystem::new_from_tag("hex").unwrap();
+        let budu_to_hex = BibiCoder::new(budu, hex);
+        let test = budu_to_hex
+            .swap(
+                "Fi-Xa-Du-Do-Ji-Li-Ri-Ro-Mu-Vo-Gu-Vi-Mu-Do-Fi-Pu-Sa-Ni-Mo-Ga-Fu-Gu-Du-Lo-Ju-So-So",
+            )
+            .unwrap();
+        assert_eq!(
+            test, "0xde0b295669a9fd93d5f28d9ec85e40f4cb697bae",
+            "test 6 2"
+        );
+        assert!(budu_to_hex.swap("Fi-Xa-").is_err(), "test 6 3");
+        assert!(budu_to_hex.swap("FiXa").is_err(), "test 6 4");
+
+        let sixty: Vec<String> = (0..60).map(|n| format!("{:02}", n)).collect();
+        let sixty = NumeralSystem::new_from_strings_with_separator(
+            String::from(""),
+            String::from(":"),
+            vec![sixty],
+        )
+        .unwrap();
+        let dec = NumeralSystem::new_from_tag("dec").unwrap();
+        let dec_to_sixty = BibiCoder::new(dec, sixty.clone());
+        assert_eq!(dec_to_sixty.swap("45296").unwrap(), "12:34:56", "test 6 5");
+        let dec = NumeralSystem::new_from_tag("dec").unwrap();
+        let sixty_to_dec = BibiCoder::new(sixty, dec);
+        assert_eq!(sixty_to_dec.swap("12:34:56").unwrap(), "45296", "test 6 6");
+
+        // ambiguous digits are allowed with a separator
+        let mut words =
+            NumeralSystem::new_with_separator("", " ", vec![vec!["no", "yes", "yesno"]]).unwrap();
+        let dec = NumeralSystem::new_from_tag("dec").unwrap();
+        let dec_to_words = BibiCoder::new(dec, words.clone());
+        assert_eq!(dec_to_words.swap("5").unwrap(), "yes yesno", "test 6 7");
+        assert!(words.set_separator("").is_err(), "test 6 8");
+        assert!(words.set_separator("s").is_err(), "test 6 9");
+        assert_eq!(words.get_separator(), " ", "test 6 10");
+    }
+
+    #[test]
+    fn test_leading_zeros() {
+        let hex = NumeralSystem::new_from_tag("hex").unwrap();
+        let dec = NumeralSystem::new_from_tag("dec").unwrap();
+        let mut hex_to_dec = BibiCoder::new(hex, dec);
+        assert_eq!(
+            hex_to_dec.get_leading_zeros(),
+            LeadingZeros::Drop,
+            "test 7 1"
+        );
+        assert_eq!(hex_to_dec.swap("0001").unwrap(), "1", "test 7 2");
+
+        hex_to_dec.set_leading_zeros(LeadingZeros::Preserve);
+        assert_eq!(hex_to_dec.swap("0001").unwrap(), "0001", "test 7 3");
+        assert_eq!(hex_to_dec.swap("00ff").unwrap(), "00255", "test 7 4");
+        assert_eq!(hex_to_dec.swap("000").unwrap(), "000", "test 7 5");
+
+        let hex = NumeralSystem::new_from_tag("hex").unwrap();
+        let base58 = NumeralSystem::new_from_tag("base58").unwrap();
+        let mut hex_to_base58 = BibiCoder::new(hex, base58);
+        hex_to_base58.set_leading_zeros(LeadingZeros::ByteAligned);
+        assert_eq!(
+            hex_to_base58
+                .swap("00000000000000000000000000000000000000000000000000")
+                .unwrap(),
+            "1111111111111111111111111",
+            "test 7 6"
+        );
+        assert_eq!(
+            hex_to_base58
+                .swap("0x00eb15231dfceb60925886b67d065299925915aeb172c06647")
+                .unwrap(),
+            "1NS17iag9jJgTHD1VXjvLCEnZuQ3rJDE9L",
+            "test 7 7"
+        );
+        assert_eq!(hex_to_base58.swap("000").unwrap(), "1", "test 7 8");
+
+        let base58 = NumeralSystem::new_from_tag("base58").unwrap();
+        let hex = NumeralSystem::new_from_tag("hex").unwrap();
+        let mut base58_to_hex = BibiCoder::new(base58, hex);
+        base58_to_hex.set_leading_zeros(LeadingZeros::ByteAligned);
+        assert_eq!(
+            base58_to_hex
+                .swap("1NS17iag9jJgTHD1VXjvLCEnZuQ3rJDE9L")
+                .unwrap(),
+            "0x00eb15231dfceb60925886b67d065299925915aeb172c06647",
+            "test 7 9"
+        );
+        assert_eq!(base58_to_hex.swap("11").unwrap(), "0x0000", "test 7 10");
+        assert_eq!(base58_to_hex.swap("5R").unwrap(), "0x0100", "test 7 11");
+    }
+
+    #[test]
+    fn test_bytes() {
+        let base58 = NumeralSystem::new_from_tag("base58").unwrap();
+        let bytes = vec![
+            0x00, 0xeb, 0x15, 0x23, 0x1d, 0xfc, 0xeb, 0x60, 0x92, 0x58, 0x86, 0xb6, 0x7d, 0x06,
+            0x52, 0x99, 0x92, 0x59, 0x15, 0xae, 0xb1, 0x72, 0xc0, 0x66, 0x47,
+        ];
+        let test = base58.encode_bytes(&bytes).unwrap();
+        assert_eq!(test, "1NS17iag9jJgTHD1VXjvLCEnZuQ3rJDE9L", "test 8 1");
+        assert_eq!(base58.decode_bytes(&test).unwrap(), bytes, "test 8 2");
+
+        assert_eq!(base58.encode_bytes(&[]).unwrap(), "", "test 8 3");
+        assert_eq!(base58.encode_bytes(&[0, 0]).unwrap(), "11", "test 8 4");
+        assert_eq!(base58.decode_bytes("").unwrap(), vec![], "test 8 5");
+        assert_eq!(base58.decode_bytes("11").unwrap(), vec![0, 0], "test 8 6");
+        assert_eq!(base58.decode_bytes("5R").unwrap(), vec![1, 0], "test 8 7");
+        assert!(base58.decode_bytes("0").is_err(), "test 8 8");
+
+        let hex = NumeralSystem::new_from_tag("hex").unwrap();
+        assert_eq!(
+            hex.encode_bytes(&[0, 1, 0xab]).unwrap(),
+            "0x0001ab",
+            "test 8 9"
+        );
+        assert_eq!(
+            hex.decode_bytes("0x0001ab").unwrap(),
+            vec![0, 1, 0xab],
+            "test 8 10"
+        );
+        assert_eq!(hex.decode_bytes("1ab").unwrap(), vec![1, 0xab], "test 8 11");
+
+        let bibi = NumeralSystem::new_from_tag("bibi").unwrap();
+        let bytes: Vec<u8> = (0..=255).collect();
+        let test = bibi.encode_bytes(&bytes).unwrap();
+        assert_eq!(bibi.decode_bytes(&test).unwrap(), bytes, "test 8 12");
+    }
+
+    #[test]
+    fn test_native() {
+        let hex = NumeralSystem::new_from_tag("hex").unwrap();
+        assert_eq!(hex.format_u128(0).unwrap(), "0x0", "test 9 1");
+        assert_eq!(hex.format_u64(2000).unwrap(), "0x7d0", "test 9 2");
+        assert_eq!(
+            hex.format_u128(u128::MAX).unwrap(),
+            "0xffffffffffffffffffffffffffffffff",
+            "test 9 3"
+        );
+        assert_eq!(hex.parse_u8("0xff").unwrap(), 255, "test 9 4");
+        assert!(
+            matches!(hex.parse_u8("0x100"), Err(BibiError::Overflow)),
+            "test 9 5"
+        );
+        assert_eq!(
+            hex.parse_u128("0xffffffffffffffffffffffffffffffff")
+                .unwrap(),
+            u128::MAX,
+            "test 9 6"
+        );
+        assert!(
+            matches!(
+                hex.parse_u128("0x1ffffffffffffffffffffffffffffffff"),
+                Err(BibiError::Overflow)
+            ),
+            "test 9 7"
+        );
+        assert_eq!(
+            hex.parse_u32("0x00000000000000000000000000000000000000ff")
+                .unwrap(),
+            255,
+            "test 9 8"
+        );
+
+        let bibi = NumeralSystem::new_from_tag("bibi").unwrap();
+        assert_eq!(bibi.format_u16(2000).unwrap(), "BIDAHO", "test 9 9");
+        assert_eq!(bibi.parse_usize("BIDAHO").unwrap(), 2000, "test 9 10");
+
+        assert_eq!(hex.format_i8(-128).unwrap(), "-0x80", "test 9 11");
+        assert_eq!(hex.format_i64(31).unwrap(), "0x1f", "test 9 12");
+        assert_eq!(hex.parse_i8("-0x80").unwrap(), -128, "test 9 13");
+        assert!(
+            matches!(hex.parse_i8("0x80"), Err(BibiError::Overflow)),
+            "test 9 14"
+        );
+        assert_eq!(
+            hex.parse_i128("-0x80000000000000000000000000000000")
+                .unwrap(),
+            i128::MIN,
+            "test 9 15"
+        );
+        assert!(
+            matches!(
+                hex.parse_i128("0x80000000000000000000000000000000"),
+                Err(BibiError::Overflow)
+            ),
+            "test 9 16"
+        );
+        assert_eq!(hex.parse_i32("+0x1").unwrap(), 1, "test 9 17");
+    }
+
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn test_biguint() {
+        let dec = NumeralSystem::new_from_tag("dec").unwrap();
+        let hex = NumeralSystem::new_from_tag("hex").unwrap();
+        let number = dec
+            .parse_biguint("324439924324324235436544328757654635345424324543")
+            .unwrap();
+        assert_eq!(
+            hex.format_biguint(&number).unwrap(),
+            "0x38d463ad8fa67a74d6e9a610158623c60d2297bf",
+            "test 10 1"
+        );
+        assert_eq!(
+            dec.format_biguint(&BigUint::default()).unwrap(),
+            "0",
+            "test 10 2"
+        );
+        assert_eq!(
+            dec.parse_biguint("0").unwrap(),
+            BigUint::default(),
+            "test 10 3"
+        );
+    }
+
+    #[test]
+    fn test_signs() {
+        let hex = NumeralSystem::new_from_tag("hex").unwrap();
+        let dec = NumeralSystem::new_from_tag("dec").unwrap();
+        let hex_to_dec = BibiCoder::new(hex, dec);
+        assert_eq!(hex_to_dec.swap("-0x1f").unwrap(), "-31", "test 11 1");
+        assert_eq!(hex_to_dec.swap("-1f").unwrap(), "-31", "test 11 2");
+        assert_eq!(hex_to_dec.swap("+0x1f").unwrap(), "31", "test 11 3");
+        assert_eq!(hex_to_dec.swap("-0").unwrap(), "0", "test 11 4");
+        assert!(hex_to_dec.swap("0x-1f").is_err(), "test 11 5");
+        assert!(hex_to_dec.swap("-").is_err(), "test 11 6");
+
+        let bibi = NumeralSystem::new_from_tag("bibi").unwrap();
+        let hex = NumeralSystem::new_from_tag("hex").unwrap();
+        let bibi_to_hex = BibiCoder::new(bibi, hex);
+        assert_eq!(bibi_to_hex.swap("−HAHO").unwrap(), "-0x10", "test 11 7");
+
+        // "-" used as a digit : only "+" and "−" are signs
+        let sys = NumeralSystem::new("", vec![vec!["-", "+", "|"]]);
+        assert!(sys.is_ok(), "test 11 8");
+        let sys = NumeralSystem::new("", vec![vec!["-", "|"]]).unwrap();
+        assert_eq!(
+            sys.get_signs(),
+            (vec![String::from("−")], vec![String::from("+")]),
+            "test 11 9"
+        );
+        let dec = NumeralSystem::new_from_tag("dec").unwrap();
+        let sys_to_dec = BibiCoder::new(sys, dec);
+        assert_eq!(sys_to_dec.swap("|--").unwrap(), "4", "test 11 10");
+        assert_eq!(sys_to_dec.swap("−|--").unwrap(), "-4", "test 11 11");
+
+        let mut sys = NumeralSystem::new("", vec![vec!["-", "|"]]).unwrap();
+        assert!(sys.set_signs(vec!["-"], vec![]).is_err(), "test 11 12");
+        assert!(sys.set_signs(vec!["|-"], vec![]).is_err(), "test 11 18");
+        sys.set_signs(vec!["n"], vec!["p"]).unwrap();
+        let mut dec = NumeralSystem::new_from_tag("dec").unwrap();
+        let dec_to_sys = BibiCoder::new(dec.clone(), sys);
+        assert_eq!(dec_to_sys.swap("-4").unwrap(), "n|--", "test 11 13");
+
+        dec.set_signs(vec![], vec![]).unwrap();
+        let hex = NumeralSystem::new_from_tag("hex").unwrap();
+        let hex_to_dec = BibiCoder::new(hex, dec);
+        assert!(
+            matches!(hex_to_dec.swap("-1"), Err(BibiError::NegativeNotSupported)),
+            "test 11 14"
+        );
+
+        let hex = NumeralSystem::new_from_tag("hex").unwrap();
+        let dec = NumeralSystem::new_from_tag("dec").unwrap();
+        let test = NumeralSystem::autodetect("-0x1f", vec![&dec, &hex]);
+        assert_eq!(test, Some(&hex), "test 11 15");
+        // no sign is invented for numeral systems without minus sign
+        let mut hex = NumeralSystem::new_from_tag("hex").unwrap();
+        hex.set_signs(vec![], vec![]).unwrap();
+        assert!(
+            matches!(hex.format_i128(-5), Err(BibiError::NegativeNotSupported)),
+            "test 11 16"
+        );
+        assert_eq!(hex.format_i8(5).unwrap(), "0x5", "test 11 17");
+    }
+
+    #[test]
+    fn test_signed_encoding() {
+        let hex = NumeralSystem::new_from_tag("hex").unwrap();
+        let dec = NumeralSystem::new_from_tag("dec").unwrap();
+        let mut hex_to_dec = BibiCoder::new(hex, dec);
+        hex_to_dec.set_signed(Some((SignedEncoding::TwosComplement, 32)));
+        assert_eq!(hex_to_dec.swap("0xfffffff6").unwrap(), "-10", "test 12 1");
+        assert_eq!(
+            hex_to_dec.swap("0x7fffffff").unwrap(),
+            "2147483647",
+            "test 12 2"
+        );
+        assert_eq!(
+            hex_to_dec.swap("0x80000000").unwrap(),
+            "-2147483648",
+            "test 12 3"
+        );
+        assert!(
+            matches!(hex_to_dec.swap("0x1fffffff6"), Err(BibiError::Overflow)),
+            "test 12 4"
+        );
+        hex_to_dec.set_signed(Some((SignedEncoding::OnesComplement, 32)));
+        assert_eq!(hex_to_dec.swap("0xfffffff5").unwrap(), "-10", "test 12 5");
+        assert_eq!(hex_to_dec.swap("0xffffffff").unwrap(), "0", "test 12 6");
+        hex_to_dec.set_signed(Some((SignedEncoding::SignMagnitude, 32)));
+        assert_eq!(hex_to_dec.swap("0x8000000a").unwrap(), "-10", "test 12 7");
+
+        let dec = NumeralSystem::new_from_tag("dec").unwrap();
+        let bin = NumeralSystem::new_from_tag("bin").unwrap();
+        let mut dec_to_bin = BibiCoder::new(dec, bin);
+        dec_to_bin.set_signed(Some((SignedEncoding::TwosComplement, 8)));
+        dec_to_bin.set_bit_patterns(BitPatterns::Output);
+        assert_eq!(dec_to_bin.swap("-10").unwrap(), "0b11110110", "test 12 8");
+        assert_eq!(dec_to_bin.swap("+10").unwrap(), "0b00001010", "test 12 9");
+        assert_eq!(dec_to_bin.swap("-128").unwrap(), "0b10000000", "test 12 10");
+        assert!(
+            matches!(dec_to_bin.swap("-129"), Err(BibiError::Overflow)),
+            "test 12 11"
+        );
+        assert!(
+            matches!(dec_to_bin.swap("+128"), Err(BibiError::Overflow)),
+            "test 12 12"
+        );
+        dec_to_bin.set_signed(Some((SignedEncoding::OnesComplement, 8)));
+        assert_eq!(dec_to_bin.swap("-10").unwrap(), "0b11110101", "test 12 13");
+        assert!(
+            matches!(dec_to_bin.swap("-128"), Err(BibiError::Overflow)),
+            "test 12 14"
+        );
+        dec_to_bin.set_signed(Some((SignedEncoding::SignMagnitude, 8)));
+        assert_eq!(dec_to_bin.swap("-10").unwrap(), "0b10001010", "test 12 15");
+        assert_eq!(dec_to_bin.swap("-0").unwrap(), "0b00000000", "test 12 16");
+
+        let dec = NumeralSystem::new_from_tag("dec").unwrap();
+        let hex = NumeralSystem::new_from_tag("hex").unwrap();
+        let mut dec_to_hex = BibiCoder::new(dec, hex);
+        dec_to_hex.set_signed(Some((SignedEncoding::TwosComplement, 32)));
+        dec_to_hex.set_bit_patterns(BitPatterns::Output);
+        assert_eq!(dec_to_hex.swap("-10").unwrap(), "0xfffffff6", "test 12 17");
+        assert_eq!(dec_to_hex.swap("+1").unwrap(), "0x00000001", "test 12 18");
+        assert_eq!(dec_to_hex.swap("1").unwrap(), "0x00000001", "test 12 19");
+
+        // the side of the bit patterns does not depend on the sign of the entry
+        dec_to_bin.set_signed(Some((SignedEncoding::TwosComplement, 8)));
+        assert!(
+            matches!(dec_to_bin.swap("200"), Err(BibiError::Overflow)),
+            "test 12 20"
+        );
+        dec_to_bin.set_bit_patterns(BitPatterns::Input);
+        assert_eq!(dec_to_bin.swap("200").unwrap(), "-0b111000", "test 12 21");
+        assert_eq!(dec_to_bin.swap("+200").unwrap(), "-0b111000", "test 12 22");
+        assert!(
+            matches!(dec_to_bin.swap("-56"), Err(BibiError::NegativeNotSupported)),
+            "test 12 23"
+        );
+    }
+
+    #[test]
+    fn test_fractions() {
+        let hex = NumeralSystem::new_from_tag("hex").unwrap();
+        let dec = NumeralSystem::new_from_tag("dec").unwrap();
+        let hex_to_dec = BibiCoder::new(hex.clone(), dec.clone());
+        assert_eq!(
+            hex_to_dec.swap_with_exactness("0x1.8").unwrap(),
+            (String::from("1.5"), true),
+            "test 13 1"
+        );
+        assert_eq!(hex_to_dec.swap("-0x1.8").unwrap(), "-1.5", "test 13 2");
+        assert_eq!(hex_to_dec.swap("0x0.80").unwrap(), "0.5", "test 13 3");
+        assert_eq!(hex_to_dec.swap("0x10.").unwrap(), "16", "test 13 4");
+        assert!(hex_to_dec.swap("0x.").is_err(), "test 13 5");
+
+        let dec_to_hex = BibiCoder::new(dec.clone(), hex.clone());
+        assert_eq!(
+            dec_to_hex.swap_with_exactness("3.14159").unwrap(),
+            (String::from("0x3.243f3e0370cdc8754f37"), false),
+            "test 13 6"
+        );
+        assert_eq!(dec_to_hex.swap(".5").unwrap(), "0x0.8", "test 13 7");
+
+        let bin = NumeralSystem::new_from_tag("bin").unwrap();
+        let mut dec_to_bin = BibiCoder::new(dec.clone(), bin);
+        dec_to_bin.set_precision(8);
+        assert_eq!(dec_to_bin.swap("0.1").unwrap(), "0b0.00011001", "test 13 8");
+        dec_to_bin.set_rounding(Rounding::HalfEven);
+        assert_eq!(dec_to_bin.swap("0.1").unwrap(), "0b0.0001101", "test 13 9");
+
+        let mut dec_to_dec = BibiCoder::new(dec.clone(), dec.clone());
+        dec_to_dec.set_rounding(Rounding::HalfEven);
+        dec_to_dec.set_precision(1);
+        assert_eq!(dec_to_dec.swap("0.99").unwrap(), "1", "test 13 10");
+        assert_eq!(dec_to_dec.swap("0.25").unwrap(), "0.2", "test 13 11");
+        assert_eq!(dec_to_dec.swap("0.35").unwrap(), "0.4", "test 13 12");
+        assert_eq!(dec_to_dec.swap("9.96").unwrap(), "10", "test 13 13");
+        dec_to_dec.set_precision(0);
+        assert_eq!(dec_to_dec.swap("2.5").unwrap(), "2", "test 13 14");
+        assert_eq!(
+            dec_to_dec.swap_with_exactness("3.5").unwrap(),
+            (String::from("4"), false),
+            "test 13 15"
+        );
+
+        assert!(
+            matches!(dec.parse_u128("1.5"), Err(BibiError::FractionNotSupported)),
+            "test 13 16"
+        );
+        let tally = NumeralSystem::new("", vec![vec![".", "|"]]).unwrap();
+        assert_eq!(tally.get_radix_point(), "", "test 13 17");
+        let dec_to_tally = BibiCoder::new(dec.clone(), tally);
+        assert!(
+            matches!(
+                dec_to_tally.swap("1.5"),
+                Err(BibiError::FractionNotSupported)
+            ),
+            "test 13 18"
+        );
+
+        let mut comma = NumeralSystem::new_from_tag("dec").unwrap();
+        assert!(comma.set_radix_point("5").is_err(), "test 13 19");
+        comma.set_radix_point(",").unwrap();
+        let comma_to_hex = BibiCoder::new(comma, hex);
+        assert_eq!(comma_to_hex.swap("3,25").unwrap(), "0x3.4", "test 13 20");
+    }
+
+    #[test]
+    fn test_rationals() {
+        let dec = NumeralSystem::new_from_tag("dec").unwrap();
+        let bin = NumeralSystem::new_from_tag("bin").unwrap();
+        let hex = NumeralSystem::new_from_tag("hex").unwrap();
+        let dec_to_dec = BibiCoder::new(dec.clone(), dec.clone());
+        assert_eq!(
+            dec_to_dec.swap_rational("1/3").unwrap(),
+            "0.(3)",
+            "test 14 1"
+        );
+        assert_eq!(
+            dec_to_dec.swap_rational("1/6").unwrap(),
+            "0.1(6)",
+            "test 14 2"
+        );
+        assert_eq!(
+            dec_to_dec.swap_rational("3/4").unwrap(),
+            "0.75",
+            "test 14 3"
+        );
+        assert_eq!(dec_to_dec.swap_rational("6/3").unwrap(), "2", "test 14 4");
+        assert_eq!(
+            dec_to_dec.swap_rational("22/7").unwrap(),
+            "3.(142857)",
+            "test 14 5"
+        );
+        assert_eq!(
+            dec_to_dec.swap_rational("-1/3").unwrap(),
+            "-0.(3)",
+            "test 14 6"
+        );
+        assert_eq!(
+            dec_to_dec.swap_rational("1/-8").unwrap(),
+            "-0.125",
+            "test 14 7"
+        );
+        assert_eq!(dec_to_dec.swap_rational("-0/5").unwrap(), "0", "test 14 8");
+        assert!(
+            matches!(
+                dec_to_dec.swap_rational("1/0"),
+                Err(BibiError::DivisionByZero)
+            ),
+            "test 14 9"
+        );
+        assert!(dec_to_dec.swap_rational("13").is_err(), "test 14 10");
+        assert!(
+            matches!(
+                dec_to_dec.swap_rational("1.5/3"),
+                Err(BibiError::FractionNotSupported)
+            ),
+            "test 14 11"
+        );
+
+        let mut dec_to_bin = BibiCoder::new(dec.clone(), bin);
+        assert_eq!(
+            dec_to_bin.swap_rational("1/10").unwrap(),
+            "0b0.0(0011)",
+            "test 14 12"
+        );
+        dec_to_bin.set_repeat_marker("[", "]");
+        assert_eq!(
+            dec_to_bin.swap_rational("1/10").unwrap(),
+            "0b0.0[0011]",
+            "test 14 13"
+        );
+
+        let hex_to_dec = BibiCoder::new(hex, dec);
+        assert_eq!(
+            hex_to_dec
+                .swap_rational("0x1/0x100000000000000000000")
+                .unwrap(),
+            "0.00000000000000000000000082718061255302767487140869206996285356581211090087890625",
+            "test 14 14"
+        );
+
+        let sexa = NumeralSystem::new_with_separator(
+            "",
+            ":",
+            vec![(0..60)
+                .map(|idx| format!("{:02}", idx))
+                .collect::<Vec<_>>()
+                .iter()
+                .map(|digit| &digit[..])
+                .collect()],
+        )
+        .unwrap();
+        let sexa_to_sexa = BibiCoder::new(sexa.clone(), sexa);
+        assert_eq!(
+            sexa_to_sexa.swap_rational("01/07").unwrap(),
+            "00.(08:34:17)",
+            "test 14 15"
+        );
+    }
+
+    // swap each number of the cases and its expected writing back and forth
+    fn check_cases(
+        coder: &BibiCoder,
+        back: &BibiCoder,
+        cases: &[(&str, &str)],
+        test: &str,
+        test_back: &str,
+    ) {
+        for (idx, (number, expected)) in cases.iter().enumerate() {
+            assert_eq!(coder.swap(number).unwrap(), *expected, "{} {}", test, idx);
+            assert_eq!(
+                back.swap(expected).unwrap(),
+                *number,
+                "{} {}",
+                test_back,
+                idx
+            );
+        }
+    }
+
+    #[test]
+    fn test_bijective() {
+        let dec = NumeralSystem::new_from_tag("dec").unwrap();
+        let excel = NumeralSystem::new_from_tag("excel").unwrap();
+        assert!(excel.is_bijective(), "test 15 1");
+        let dec_to_excel = BibiCoder::new(dec.clone(), excel.clone());
+        let excel_to_dec = BibiCoder::new(excel.clone(), dec.clone());
+        let cases = [
+            ("1", "A"),
+            ("26", "Z"),
+            ("27", "AA"),
+            ("52", "AZ"),
+            ("702", "ZZ"),
+            ("703", "AAA"),
+            ("16384", "XFD"),
+            ("0", ""),
+        ];
+        check_cases(
+            &dec_to_excel,
+            &excel_to_dec,
+            &cases,
+            "test 15 2",
+            "test 15 3",
+        );
+        assert_eq!(dec_to_excel.swap("-28").unwrap(), "-AB", "test 15 4");
+        assert_eq!(excel.parse_u32("XFD").unwrap(), 16384, "test 15 5");
+        assert_eq!(excel.format_u64(18278).unwrap(), "ZZZ", "test 15 6");
+        assert!(
+            matches!(
+                dec_to_excel.swap("1.5"),
+                Err(BibiError::FractionNotSupported)
+            ),
+            "test 15 7"
+        );
+
+        let mut bij2 = NumeralSystem::new("#", vec![vec!["1", "2"]]).unwrap();
+        bij2.set_bijective(true).unwrap();
+        let dec_to_bij2 = BibiCoder::new(dec.clone(), bij2.clone());
+        assert_eq!(dec_to_bij2.swap("5").unwrap(), "#21", "test 15 8");
+        assert_eq!(dec_to_bij2.swap("6").unwrap(), "#22", "test 15 9");
+        assert_eq!(dec_to_bij2.swap("7").unwrap(), "#111", "test 15 10");
+        let bij2_to_dec = BibiCoder::new(bij2, dec.clone());
+        assert_eq!(bij2_to_dec.swap("#").unwrap(), "0", "test 15 11");
+        assert_eq!(bij2_to_dec.swap("#212").unwrap(), "12", "test 15 12");
+
+        let mut unary = NumeralSystem::new("", vec![vec!["|"]]).unwrap();
+        assert!(unary.set_bijective(true).is_err(), "test 15 13");
+
+        let mut bij10 = NumeralSystem::new_from_tag("dec").unwrap();
+        bij10.set_bijective(true).unwrap();
+        assert_eq!(bij10.get_radix_point(), "", "test 15 14");
+        bij10.set_bijective(false).unwrap();
+        assert_eq!(bij10.get_radix_point(), ".", "test 15 15");
+        let bij10_to_dec = BibiCoder::new(bij10, dec.clone());
+        assert_eq!(bij10_to_dec.swap("1.5").unwrap(), "1.5", "test 15 16");
+    }
+
+    #[test]
+    fn test_negative_base() {
+        let dec = NumeralSystem::new_from_tag("dec").unwrap();
+        let negabin = NumeralSystem::new_from_tag("negabin").unwrap();
+        let negadec = NumeralSystem::new_from_tag("negadec").unwrap();
+        assert!(negabin.is_negative_base(), "test 16 1");
+        let dec_to_negabin = BibiCoder::new(dec.clone(), negabin.clone());
+        let negabin_to_dec = BibiCoder::new(negabin.clone(), dec.clone());
+        let cases = [
+            ("0", "0"),
+            ("1", "1"),
+            ("2", "110"),
+            ("3", "111"),
+            ("4", "100"),
+            ("6", "11010"),
+            ("-1", "11"),
+            ("-2", "10"),
+            ("-3", "1101"),
+        ];
+        check_cases(
+            &dec_to_negabin,
+            &negabin_to_dec,
+            &cases,
+            "test 16 2",
+            "test 16 3",
+        );
+
+        let dec_to_negadec = BibiCoder::new(dec.clone(), negadec.clone());
+        assert_eq!(dec_to_negadec.swap("10").unwrap(), "190", "test 16 4");
+        assert_eq!(dec_to_negadec.swap("15").unwrap(), "195", "test 16 5");
+        assert_eq!(dec_to_negadec.swap("-15").unwrap(), "25", "test 16 6");
+        assert_eq!(dec_to_negadec.swap("-10").unwrap(), "10", "test 16 7");
+
+        let negadec_to_negabin = BibiCoder::new(negadec.clone(), negabin.clone());
+        assert_eq!(
+            negadec_to_negabin.swap("25").unwrap(),
+            "110001",
+            "test 16 8"
+        );
+        assert_eq!(negadec.parse_i32("25").unwrap(), -15, "test 16 9");
+        assert_eq!(negabin.format_i8(-128).unwrap(), "10000000", "test 16 10");
+        assert!(
+            matches!(negadec.parse_u32("25"), Err(BibiError::Overflow)),
+            "test 16 11"
+        );
+        assert_eq!(
+            negadec
+                .parse_i128(&negadec.format_i128(i128::MIN).unwrap())
+                .unwrap(),
+            i128::MIN,
+            "test 16 12"
+        );
+
+        let mut excel = NumeralSystem::new_from_tag("excel").unwrap();
+        assert!(excel.set_negative_base(true).is_err(), "test 16 13");
+
+        // one numeration at a time, the default radix point coming back with standard numeration
+        excel.set_negative_base(false).unwrap();
+        assert!(excel.is_bijective(), "test 16 14");
+        let mut negadec = negadec.clone();
+        assert!(negadec.set_bijective(true).is_err(), "test 16 15");
+        assert!(negadec.set_values(vec![0; 10], 10).is_err(), "test 16 16");
+        negadec.set_negative_base(false).unwrap();
+        assert_eq!(negadec.get_radix_point(), ".", "test 16 17");
+        negadec.set_bijective(true).unwrap();
+        assert!(negadec.is_bijective(), "test 16 18");
+    }
+
+    #[test]
+    fn test_digit_values() {
+        let dec = NumeralSystem::new_from_tag("dec").unwrap();
+        let mut balanced = NumeralSystem::new("", vec![vec!["-", "0", "+"]]).unwrap();
+        balanced.set_values(vec![-1, 0, 1], 3).unwrap();
+        assert_eq!(balanced.radix(), Some(3), "test 17 1");
+        let dec_to_balanced = BibiCoder::new(dec.clone(), balanced.clone());
+        let balanced_to_dec = BibiCoder::new(balanced.clone(), dec.clone());
+        let cases = [
+            ("0", "0"),
+            ("1", "+"),
+            ("2", "+-"),
+            ("3", "+0"),
+            ("5", "+--"),
+            ("8", "+0-"),
+            ("100", "++-0+"),
+            ("-1", "-"),
+            ("-2", "-+"),
+            ("-7", "-+-"),
+        ];
+        check_cases(
+            &dec_to_balanced,
+            &balanced_to_dec,
+            &cases,
+            "test 17 2",
+            "test 17 3",
+        );
+        assert_eq!(balanced.parse_i16("--").unwrap(), -4, "test 17 4");
+        assert!(
+            matches!(balanced.parse_u16("--"), Err(BibiError::Overflow)),
+            "test 17 5"
+        );
+
+        // redundant binary
+        let mut redundant = NumeralSystem::new("", vec![vec!["0", "1", "2"]]).unwrap();
+        redundant.set_values(vec![0, 1, 2], 2).unwrap();
+        let redundant_to_dec = BibiCoder::new(redundant.clone(), dec.clone());
+        assert_eq!(redundant_to_dec.swap("122").unwrap(), "10", "test 17 6");
+        let dec_to_redundant = BibiCoder::new(dec.clone(), redundant);
+        assert_eq!(dec_to_redundant.swap("10").unwrap(), "1010", "test 17 7");
+        assert_eq!(dec_to_redundant.swap("-10").unwrap(), "-1010", "test 17 8");
+
+        // no zero digit
+        let mut nozero = NumeralSystem::new("", vec![vec!["a", "b", "c"]]).unwrap();
+        nozero.set_values(vec![1, 2, 3], 3).unwrap();
+        let dec_to_nozero = BibiCoder::new(dec.clone(), nozero);
+        assert_eq!(dec_to_nozero.swap("3").unwrap(), "c", "test 17 9");
+        assert_eq!(dec_to_nozero.swap("12").unwrap(), "cc", "test 17 10");
+
+        let mut bad = NumeralSystem::new("", vec![vec!["a", "b", "c"]]).unwrap();
+        assert!(bad.set_values(vec![0, -2, 2], 3).is_err(), "test 17 11");
+        assert!(bad.set_values(vec![0, 1, 4], 3).is_err(), "test 17 12");
+        assert!(bad.set_values(vec![0, 1], 3).is_err(), "test 17 13");
+        assert!(bad.set_values(vec![0, 1, 1], 2).is_err(), "test 17 14");
+        assert!(bad.set_values(vec![0, 1, -1], 1).is_err(), "test 17 15");
+        assert!(bad.set_values(vec![0, 1, 9], 3).is_err(), "test 17 16");
+        assert!(
+            bad.set_values(vec![0, 1, 50_000_000], 2).is_err(),
+            "test 17 17"
+        );
+        bad.set_values(vec![1, 2, 3], 3).unwrap();
+        assert_eq!(bad.get_radix_point(), "", "test 17 18");
+        bad.set_values(vec![], 0).unwrap();
+        assert_eq!(bad.get_radix_point(), ".", "test 17 19");
+        assert_eq!(bad.radix(), Some(3), "test 17 20");
+        // a residue would have no digit, whatever the values
+        assert!(
+            matches!(
+                bad.set_values(vec![1, 2, 3], 1_000_000_000_000),
+                Err(BibiError::BadNumeralSystem)
+            ),
+            "test 17 21"
+        );
+        assert!(
+            matches!(
+                bad.set_values(vec![1, 2, 3], usize::MAX),
+                Err(BibiError::BadNumeralSystem)
+            ),
+            "test 17 22"
+        );
+    }
+
+    #[test]
+    fn test_mixed_radix() {
+        let dec = NumeralSystem::new_from_tag("dec").unwrap();
+        let duration = NumeralSystem::new_from_tag("duration").unwrap();
+        assert!(duration.is_mixed_radix(), "test 18 1");
+        let dec_to_duration = BibiCoder::new(dec.clone(), duration.clone());
+        let duration_to_dec = BibiCoder::new(duration.clone(), dec.clone());
+        let cases = [
+            ("0", "00"),
+            ("59", "59"),
+            ("60", "01:00"),
+            ("3723", "01:02:03"),
+            ("86399", "23:59:59"),
+            ("93784", "1:02:03:04"),
+            ("8640000", "100:00:00:00"),
+            ("-61", "-01:01"),
+        ];
+        check_cases(
+            &dec_to_duration,
+            &duration_to_dec,
+            &cases,
+            "test 18 2",
+            "test 18 3",
+        );
+        assert!(duration_to_dec.swap("24:00:00").is_err(), "test 18 4");
+        assert!(duration_to_dec.swap("1:60:00").is_err(), "test 18 5");
+        assert_eq!(
+            duration.parse_u32("2:00:00:00").unwrap(),
+            172800,
+            "test 18 6"
+        );
+        assert!(
+            matches!(
+                dec_to_duration.swap("1.5"),
+                Err(BibiError::FractionNotSupported)
+            ),
+            "test 18 7"
+        );
+
+        let factoradic = NumeralSystem::new_from_tag("factoradic").unwrap();
+        let dec_to_factoradic = BibiCoder::new(dec.clone(), factoradic.clone());
+        assert_eq!(
+            dec_to_factoradic.swap("463").unwrap(),
+            "341010",
+            "test 18 8"
+        );
+        assert_eq!(dec_to_factoradic.swap("5").unwrap(), "210", "test 18 9");
+        assert_eq!(factoradic.parse_u64("341010").unwrap(), 463, "test 18 10");
+        assert_eq!(
+            factoradic
+                .parse_u128(&factoradic.format_u128(u128::MAX).unwrap())
+                .unwrap(),
+            u128::MAX,
+            "test 18 11"
+        );
+        assert!(factoradic.parse_u32("201").is_err(), "test 18 12");
+
+        // week clock without head : the days are repeated in base 7
+        let week = NumeralSystem::new_mixed(
+            "",
+            "",
+            vec![vec!["0", "1", "2", "3", "4", "5", "6"], vec!["a", "b", "c"]],
+            None,
+        )
+        .unwrap();
+        let dec_to_week = BibiCoder::new(dec.clone(), week.clone());
+        assert_eq!(dec_to_week.swap("20").unwrap(), "6c", "test 18 13");
+        assert_eq!(dec_to_week.swap("21").unwrap(), "10a", "test 18 14");
+        assert_eq!(dec_to_week.swap("22").unwrap(), "10b", "test 18 15");
+        assert_eq!(week.parse_u32("16c").unwrap(), 41, "test 18 16");
+
+        assert!(
+            NumeralSystem::new_mixed("", "", vec![vec!["0"]], None).is_err(),
+            "test 18 17"
+        );
+        let mut duration = duration;
+        assert!(duration.set_separator("-").is_err(), "test 18 18");
+        assert!(duration.set_radix_point(".").is_err(), "test 18 19");
+        assert_eq!(duration.radix(), None, "test 18 20");
+        assert_eq!(
+            dec_to_duration.swap_rational("7320/2").unwrap(),
+            "01:01:00",
+            "test 18 21"
+        );
+        assert!(
+            matches!(
+                dec_to_duration.swap_rational("1/2"),
+                Err(BibiError::FractionNotSupported)
+            ),
+            "test 18 22"
+        );
+        let mut dec_to_signed = BibiCoder::new(dec.clone(), duration.clone());
+        dec_to_signed.set_signed(Some((SignedEncoding::TwosComplement, 8)));
+        dec_to_signed.set_bit_patterns(BitPatterns::Output);
+        assert_eq!(dec_to_signed.swap("-1").unwrap(), "04:15", "test 18 23");
+    }
+
+    #[test]
+    fn test_unary() {
+        let dec = NumeralSystem::new_from_tag("dec").unwrap();
+        let unary = NumeralSystem::new_from_tag("unary").unwrap();
+        assert!(unary.is_unary(), "test 19 1");
+        let dec_to_unary = BibiCoder::new(dec.clone(), unary.clone());
+        let unary_to_dec = BibiCoder::new(unary.clone(), dec.clone());
+        assert_eq!(dec_to_unary.swap("5").unwrap(), "|||||", "test 19 2");
+        assert_eq!(dec_to_unary.swap("0").unwrap(), "", "test 19 3");
+        assert_eq!(dec_to_unary.swap("-2").unwrap(), "-||", "test 19 4");
+        assert_eq!(unary_to_dec.swap("|||||||").unwrap(), "7", "test 19 5");
+        assert_eq!(unary_to_dec.swap("").unwrap(), "0", "test 19 6");
+        assert!(unary_to_dec.swap("||x").is_err(), "test 19 7");
+        assert_eq!(unary.format_u8(3).unwrap(), "|||", "test 19 8");
+        assert_eq!(unary.parse_u8("||||").unwrap(), 4, "test 19 9");
+
+        let mut tally = unary.clone();
+        tally.set_unary_group("卌").unwrap();
+        let dec_to_tally = BibiCoder::new(dec.clone(), tally.clone());
+        assert_eq!(dec_to_tally.swap("12").unwrap(), "卌卌||", "test 19 10");
+        assert_eq!(dec_to_tally.swap("4").unwrap(), "||||", "test 19 11");
+        let tally_to_dec = BibiCoder::new(tally.clone(), dec.clone());
+        assert_eq!(tally_to_dec.swap("卌卌||").unwrap(), "12", "test 19 12");
+        assert_eq!(tally_to_dec.swap("|||||卌").unwrap(), "10", "test 19 13");
+        assert!(tally.set_unary_group("|").is_err(), "test 19 14");
+        assert!(dec.clone().set_unary_group("卌").is_err(), "test 19 15");
+
+        // too long numbers are rejected before being written
+        assert!(
+            matches!(dec_to_unary.swap("1000000000000"), Err(BibiError::Overflow)),
+            "test 19 16"
+        );
+        let mut short = unary.clone();
+        short.set_length_limit(10);
+        let dec_to_short = BibiCoder::new(dec.clone(), short);
+        assert_eq!(dec_to_short.swap("10").unwrap().len(), 10, "test 19 17");
+        assert!(
+            matches!(dec_to_short.swap("11"), Err(BibiError::Overflow)),
+            "test 19 18"
+        );
+        assert!(
+            matches!(
+                unary.format_u128(u64::MAX as u128),
+                Err(BibiError::Overflow)
+            ),
+            "test 19 20"
+        );
+        assert!(
+            matches!(unary.format_i8(-11), Ok(ref marks) if marks == "-|||||||||||"),
+            "test 19 21"
+        );
+        assert!(
+            matches!(unary.encode_bytes(&[1, 0, 0, 0]), Err(BibiError::Overflow)),
+            "test 19 22"
+        );
+        let hex = NumeralSystem::new_from_tag("hex").unwrap();
+        let unary_to_hex = BibiCoder::new(unary, hex);
+        assert_eq!(
+            unary_to_hex.swap(&"|".repeat(300)).unwrap(),
+            "0x12c",
+            "test 19 19"
+        );
+    }
+
+    #[test]
+    fn test_roman() {
+        let dec = NumeralSystem::new_from_tag("dec").unwrap();
+        let roman = Roman::from_tag("roman").unwrap();
+        assert_eq!(roman.get_notation(), RomanNotation::Standard, "test 20 1");
+        let dec_to_roman = BibiCoder::new(dec.clone(), roman);
+        let roman_to_dec = BibiCoder::new(roman, dec.clone());
+        assert_eq!(dec_to_roman.swap("2019").unwrap(), "MMXIX", "test 20 2");
+        assert_eq!(dec_to_roman.swap("3999").unwrap(), "MMMCMXCIX", "test 20 3");
+        assert_eq!(dec_to_roman.swap("444").unwrap(), "CDXLIV", "test 20 4");
+        assert_eq!(roman_to_dec.swap("MCMXCIV").unwrap(), "1994", "test 20 5");
+        assert_eq!(roman_to_dec.swap("XLII").unwrap(), "42", "test 20 6");
+
+        // zero, negative and too large numbers can not be written
+        assert!(
+            matches!(dec_to_roman.swap("0"), Err(BibiError::NotRepresentable)),
+            "test 20 7"
+        );
+        assert!(
+            matches!(dec_to_roman.swap("4000"), Err(BibiError::NotRepresentable)),
+            "test 20 8"
+        );
+        assert!(
+            matches!(
+                dec_to_roman.swap("-5"),
+                Err(BibiError::NegativeNotSupported)
+            ),
+            "test 20 9"
+        );
+        assert!(
+            matches!(
+                dec_to_roman.swap("1.5"),
+                Err(BibiError::FractionNotSupported)
+            ),
+            "test 20 10"
+        );
+
+        // strict and lenient reading
+        assert!(roman_to_dec.swap("IIII").is_err(), "test 20 11");
+        assert!(roman_to_dec.swap("IC").is_err(), "test 20 12");
+        assert!(roman_to_dec.swap("xlii").is_err(), "test 20 13");
+        let mut lenient = roman;
+        lenient.set_lenient(true);
+        let lenient_to_dec = BibiCoder::new(lenient, dec.clone());
+        assert_eq!(lenient_to_dec.swap("IIII").unwrap(), "4", "test 20 14");
+        assert_eq!(lenient_to_dec.swap("IIX").unwrap(), "8", "test 20 15");
+        assert_eq!(
+            lenient_to_dec.swap("mdcccclxxxx").unwrap(),
+            "1990",
+            "test 20 16"
+        );
+        assert!(lenient_to_dec.swap("MXQ").is_err(), "test 20 17");
+
+        // large numbers
+        let vinculum = Roman::from_tag("roman-vinculum").unwrap();
+        let dec_to_vinculum = BibiCoder::new(dec.clone(), vinculum);
+        assert_eq!(
+            dec_to_vinculum.swap("12345").unwrap(),
+            "X\u{304}MMCCCXLV",
+            "test 20 18"
+        );
+        assert_eq!(
+            dec_to_vinculum.swap("3999999").unwrap(),
+            "M\u{304}M\u{304}M\u{304}C\u{304}M\u{304}X\u{304}C\u{304}MX\u{304}CMXCIX",
+            "test 20 19"
+        );
+        assert_eq!(
+            vinculum.to_pivot("V\u{304}M").unwrap().to_u128().unwrap(),
+            6000,
+            "test 20 20"
+        );
+        assert!(dec_to_vinculum.swap("4000000").is_err(), "test 20 21");
+        let apostrophus = Roman::from_tag("roman-apostrophus").unwrap();
+        let dec_to_apostrophus = BibiCoder::new(dec.clone(), apostrophus);
+        assert_eq!(
+            dec_to_apostrophus.swap("16000").unwrap(),
+            "CCIↃↃIↃↃCIↃ",
+            "test 20 22"
+        );
+        assert_eq!(
+            apostrophus.to_pivot("CCIↃ").unwrap().to_u128().unwrap(),
+            900,
+            "test 20 23"
+        );
+        assert!(dec_to_apostrophus.swap("400000").is_err(), "test 20 24");
+
+        // Roman numerals to any other numeral system
+        let hex = NumeralSystem::new_from_tag("hex").unwrap();
+        let roman_to_hex = BibiCoder::new(roman, hex);
+        assert_eq!(roman_to_hex.swap("MMXIX").unwrap(), "0x7e3", "test 20 25");
+        let roman_to_roman = BibiCoder::new(roman, vinculum);
+        assert_eq!(roman_to_roman.swap("MMMD").unwrap(), "MMMD", "test 20 26");
+        assert!(
+            matches!(
+                roman.from_pivot(&Pivot::from_u128(5000, false)),
+                Err(BibiError::NotRepresentable)
+            ),
+            "test 20 27"
+        );
+    }
+
+    #[test]
+    fn test_words() {
+        let dec = NumeralSystem::new_from_tag("dec").unwrap();
+        let en = Words::from_tag("words-en").unwrap();
+        let fr = Words::from_tag("words-fr").unwrap();
+        let dec_to_en = BibiCoder::new(dec.clone(), en);
+        let dec_to_fr = BibiCoder::new(dec.clone(), fr);
+        assert_eq!(dec_to_en.swap("2000").unwrap(), "two thousand", "test 21 1");
+        assert_eq!(dec_to_fr.swap("2000").unwrap(), "deux mille", "test 21 2");
+        assert_eq!(dec_to_en.swap("0").unwrap(), "zero", "test 21 3");
+        assert_eq!(
+            dec_to_en.swap("1234567").unwrap(),
+            "one million two hundred thirty-four thousand five hundred sixty-seven",
+            "test 21 4"
+        );
+        assert_eq!(
+            dec_to_fr.swap("280071").unwrap(),
+            "deux cent quatre-vingt mille soixante et onze",
+            "test 21 5"
+        );
+        assert_eq!(
+            dec_to_fr.swap("200000000").unwrap(),
+            "deux cents millions",
+            "test 21 6"
+        );
+
+        // long and short scales
+        assert_eq!(
+            dec_to_fr.swap("3000000000").unwrap(),
+            "trois milliards",
+            "test 21 7"
+        );
+        let short = Words::from_tag("words-fr-short").unwrap();
+        let dec_to_short = BibiCoder::new(dec.clone(), short);
+        assert_eq!(
+            dec_to_short.swap("3000000000").unwrap(),
+            "trois billions",
+            "test 21 8"
+        );
+        let en_long = Words::from_tag("words-en-long").unwrap();
+        let dec_to_en_long = BibiCoder::new(dec.clone(), en_long);
+        assert_eq!(
+            dec_to_en_long.swap("1500000000").unwrap(),
+            "one thousand five hundred million",
+            "test 21 9"
+        );
+        assert_eq!(
+            dec_to_en_long.swap("1000000000000").unwrap(),
+            "one billion",
+            "test 21 10"
+        );
+
+        // belgian and swiss variants
+        let be = Words::from_tag("words-fr-be").unwrap();
+        let ch = Words::from_tag("words-fr-ch").unwrap();
+        assert_eq!(
+            be.from_pivot(&Pivot::from_u128(1097, false)).unwrap(),
+            "mille nonante-sept",
+            "test 21 11"
+        );
+        assert_eq!(
+            be.from_pivot(&Pivot::from_u128(80, false)).unwrap(),
+            "quatre-vingts",
+            "test 21 12"
+        );
+        assert_eq!(
+            ch.from_pivot(&Pivot::from_u128(81, false)).unwrap(),
+            "huitante et un",
+            "test 21 13"
+        );
+        assert_eq!(
+            fr.from_pivot(&Pivot::from_u128(97, false)).unwrap(),
+            "quatre-vingt-dix-sept",
+            "test 21 14"
+        );
+
+        // reading words
+        let en_to_dec = BibiCoder::new(en, dec.clone());
+        let fr_to_dec = BibiCoder::new(fr, dec.clone());
+        assert_eq!(
+            en_to_dec.swap("nine hundred ninety-nine").unwrap(),
+            "999",
+            "test 21 15"
+        );
+        assert_eq!(
+            fr_to_dec.swap("quatre-vingt-dix-sept mille").unwrap(),
+            "97000",
+            "test 21 16"
+        );
+        assert!(en_to_dec.swap("hundred two").is_err(), "test 21 17");
+        assert!(en_to_dec.swap("one hundred and two").is_err(), "test 21 18");
+        let mut lenient = en;
+        lenient.set_lenient(true);
+        let lenient_to_dec = BibiCoder::new(lenient, dec.clone());
+        assert_eq!(
+            lenient_to_dec.swap("One Hundred and Two").unwrap(),
+            "102",
+            "test 21 19"
+        );
+        assert!(
+            lenient_to_dec.swap("one hundred zebra").is_err(),
+            "test 21 20"
+        );
+
+        // any number can be written and read back
+        let big = format!("12{}", "0123456789".repeat(40));
+        for words in [en, fr, en_long, be, ch] {
+            let dec_to_words = BibiCoder::new(dec.clone(), words);
+            let words_to_dec = BibiCoder::new(words, dec.clone());
+            for number in ["7", "1000001", "80080080", "999999999999", &big[..]] {
+                let written = dec_to_words.swap(number).unwrap();
+                assert_eq!(words_to_dec.swap(&written).unwrap(), number, "test 21 21");
+            }
+        }
+        assert_eq!(
+            dec_to_en.swap(&format!("1{}", "0".repeat(3003))).unwrap(),
+            "one millinillion",
+            "test 21 22"
+        );
+        assert!(
+            matches!(dec_to_en.swap("-5"), Err(BibiError::NegativeNotSupported)),
+            "test 21 23"
+        );
+    }
+
+    // decimal number followed by the sum of its digits modulo 10
+    #[derive(Debug)]
+    struct CheckDigit;
+
+    impl Codec for CheckDigit {
+        fn to_pivot(&self, entry: &str) -> Result<Pivot, BibiError> {
+            let mut digits: Vec<u32> = vec![];
+            for c in entry.chars() {
+                digits.push(
+                    c.to_digit(10)
+                        .ok_or(BibiError::EntryMismatchWithNumeralSystem)?,
+                );
+            }
+            let check = digits
+                .pop()
+                .ok_or(BibiError::EntryMismatchWithNumeralSystem)?;
+            if digits.is_empty() || digits.iter().sum::<u32>() % 10 != check {
+                return Err(BibiError::EntryMismatchWithNumeralSystem);
+            }
+            Ok(Pivot::from_digits(&digits, 10, false))
+        }
+
+        fn from_pivot(&self, pivot: &Pivot) -> Result<String, BibiError> {
+            if pivot.is_negative() && !pivot.is_zero() {
+                return Err(BibiError::NegativeNotSupported);
+            }
+            let mut digits = pivot.to_digits(10);
+            if digits.is_empty() {
+                digits.push(0);
+            }
+            digits.push(digits.iter().sum::<u32>() % 10);
+            Ok(digits.iter().map(|digit| digit.to_string()).collect())
+        }
+    }
+
+    #[test]
+    fn test_codec() {
+        let dec = NumeralSystem::new_from_tag("dec").unwrap();
+        let hex = NumeralSystem::new_from_tag("hex").unwrap();
+        let dec_to_check = BibiCoder::new(dec.clone(), CheckDigit);
+        assert_eq!(dec_to_check.swap("1234").unwrap(), "12340", "test 22 1");
+        assert_eq!(dec_to_check.swap("0").unwrap(), "00", "test 22 2");
+        assert!(
+            matches!(
+                dec_to_check.swap("-3"),
+                Err(BibiError::NegativeNotSupported)
+            ),
+            "test 22 3"
+        );
+        let check_to_hex = BibiCoder::from_codecs(Box::new(CheckDigit), Box::new(hex.clone()));
+        assert_eq!(check_to_hex.swap("12340").unwrap(), "0x4d2", "test 22 4");
+        assert!(check_to_hex.swap("12345").is_err(), "test 22 5");
 
-        let test = NumeralSystem::new("", vec![vec!["0", "1", "2", "22"]]);
-        assert!(test.is_err(), "test 1 2");
+        // numeral systems are codecs too
+        let pivot = hex.to_pivot("-0xff").unwrap();
+        assert!(pivot.is_negative(), "test 22 6");
+        assert_eq!(pivot.to_u128().unwrap(), 255, "test 22 7");
+        assert_eq!(dec.from_pivot(&pivot).unwrap(), "-255", "test 22 8");
+        assert_eq!(
+            Pivot::from_digits(&[1, 2, 3], 10, false).to_digits(16),
+            vec![7, 11],
+            "test 22 9"
+        );
+        assert!(
+            Pivot::from_u128(0, false).to_digits(10).is_empty(),
+            "test 22 10"
+        );
+        assert!(dec.numeral_system().is_some(), "test 22 11");
+        assert!(CheckDigit.numeral_system().is_none(), "test 22 12");
 
-        let test = NumeralSystem::new("", vec![vec!["0", "1", "2"], vec!["0", "1", "2", "22"]]);
-        assert!(test.is_err(), "test 1 3");
+        // from any codec to any codec
+        let roman_to_words = BibiCoder::new(
+            Roman::new(RomanNotation::Standard),
+            Words::new(Language::English, Scale::Short),
+        );
+        assert_eq!(
+            roman_to_words.swap("MMXIX").unwrap(),
+            "two thousand nineteen",
+            "test 22 13"
+        );
+        let roman_to_dec = BibiCoder::new(Roman::new(RomanNotation::Standard), dec.clone());
+        assert_eq!(
+            roman_to_dec.swap_rational("X/IV").unwrap(),
+            "2.5",
+            "test 22 14"
+        );
+        let dec_to_roman = BibiCoder::new(dec.clone(), Roman::new(RomanNotation::Standard));
+        assert_eq!(
+            dec_to_roman.swap_rational("10/5").unwrap(),
+            "II",
+            "test 22 15"
+        );
+        assert!(
+            matches!(
+                dec_to_roman.swap_rational("10/4"),
+                Err(BibiError::FractionNotSupported)
+            ),
+            "test 22 16"
+        );
 
-        let test = NumeralSystem::new("", vec![vec!["0", "1", "2"], vec!["0", "1", "2", "2"]]);
-        assert!(test.is_err(), "test 1 4");
+        // numeral systems without radix point do not write fractions, whoever calls them
+        let half = dec.to_pivot("1.5").unwrap();
+        let duration = NumeralSystem::new_from_tag("duration").unwrap();
+        assert!(
+            matches!(
+                duration.from_pivot(&half),
+                Err(BibiError::FractionNotSupported)
+            ),
+            "test 22 17"
+        );
+        let factoradic = NumeralSystem::new_from_tag("factoradic").unwrap();
+        assert!(
+            matches!(
+                factoradic.from_pivot(&half),
+                Err(BibiError::FractionNotSupported)
+            ),
+            "test 22 18"
+        );
+        let unary = NumeralSystem::new_from_tag("unary").unwrap();
+        assert!(
+            matches!(
+                unary.from_pivot(&half),
+                Err(BibiError::FractionNotSupported)
+            ),
+            "test 22 19"
+        );
+    }
 
-        let a = vec![
-            "B", "K", "D", "F", "G", "J", "L", "M", "N", "P", "R", "S", "T", "V", "X", "Z",
-        ];
-        let b = vec!["a", "i", "o", "u"];
-        let len1 = a.len() * b.len();
-        let test = NumeralSystem::new("", vec![a, b]).unwrap();
-        let len2 = test.digits.len();
-        assert!(len1 == len2, "test 1 5");
+    #[test]
+    fn test_registry() {
+        let mut registry = Registry::builtin();
+        assert!(registry.contains("hex"), "test 23 1");
+        assert!(registry.contains("duration"), "test 23 2");
+        assert!(registry.contains("roman"), "test 23 3");
+        assert!(registry.contains("words-fr-ch"), "test 23 4");
+        assert_eq!(registry.names()[..3], ["bin", "oct", "dec"], "test 23 5");
+        assert_eq!(
+            NumeralSystem::get_prefixes_from_tags(),
+            registry.get_prefixes(),
+            "test 23 6"
+        );
+
+        let (name, _) = registry.autodetect("0x1f").unwrap();
+        assert_eq!(name, "hex", "test 23 7");
+        let (name, _) = registry.autodetect("-0b101").unwrap();
+        assert_eq!(name, "bin", "test 23 8");
+        assert!(registry.autodetect("0x").is_none(), "test 23 9");
+        assert!(registry.autodetect("1234").is_none(), "test 23 10");
+
+        // longest prefix
+        let hexx = NumeralSystem::new("0xx", vec![vec!["0", "1"]]).unwrap();
+        registry.register("hexx", hexx).unwrap();
+        let (name, _) = registry.autodetect("0xx1").unwrap();
+        assert_eq!(name, "hexx", "test 23 11");
+        let (name, _) = registry.autodetect("0x1").unwrap();
+        assert_eq!(name, "hex", "test 23 12");
+
+        // collisions
+        let other = NumeralSystem::new("0x", vec![vec!["0", "1"]]).unwrap();
+        assert!(
+            matches!(
+                registry.register("other", other.clone()),
+                Err(BibiError::PrefixCollision(ref name)) if name == "hex"
+            ),
+            "test 23 13"
+        );
+        assert!(!registry.contains("other"), "test 23 14");
+        assert!(
+            matches!(
+                registry.register("hexx", CheckDigit),
+                Err(BibiError::DuplicateName(ref name)) if name == "hexx"
+            ),
+            "test 23 15"
+        );
+        assert!(registry.remove("hex").is_some(), "test 23 16");
+        registry.register("other", other).unwrap();
+        let (name, _) = registry.autodetect("0x1").unwrap();
+        assert_eq!(name, "other", "test 23 17");
+
+        // codecs without prefix
+        registry.register("check", CheckDigit).unwrap();
+        let coder = BibiCoder::new(registry.get("dec").unwrap(), registry.get("check").unwrap());
+        assert_eq!(coder.swap("1234").unwrap(), "12340", "test 23 18");
+
+        // the builtin registry is not changed
+        assert!(Registry::builtin().contains("hex"), "test 23 19");
+        assert!(!Registry::builtin().contains("check"), "test 23 20");
+        assert!(Registry::new().names().is_empty(), "test 23 21");
     }
 
     #[test]
-    fn test_bibicoder() {
-        let dec = NumeralSystem::new(
-            "",
-            vec![vec!["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"]],
-        )
-        .unwrap();
-        let bibi = NumeralSystem::new(
-            "",
-            vec![vec![
-                "HO", "HA", "HE", "HI", "BO", "BA", "BE", "BI", "KO", "KA", "KE", "KI", "DO", "DA",
-                "DE", "DI",
-            ]],
-        )
-        .unwrap();
+    fn test_rank() {
+        let mut registry = Registry::builtin();
+        let candidates = registry.rank(&["HAHOBI"]);
+        assert_eq!(candidates[0].0, "bibi", "test 24 1");
+        assert!(candidates[0].1 > 0.99, "test 24 2");
+        let total: f64 = candidates.iter().map(|(_, confidence)| confidence).sum();
+        assert!((total - 1.0).abs() < 1e-9, "test 24 3");
 
-        let dec_to_bibi = BibiCoder::new(dec, bibi);
+        // smaller alphabets first
+        let candidates = registry.rank(&["1f"]);
+        let names: Vec<&str> = candidates.iter().map(|(name, _)| *name).collect();
+        assert_eq!(names, ["hex", "base58"], "test 24 4");
+        assert!(candidates[0].1 > 0.9, "test 24 5");
+        let candidates = registry.rank(&["101"]);
+        assert_eq!(candidates[0].0, "bin", "test 24 6");
+        assert_eq!(candidates[1].0, "negabin", "test 24 7");
+        assert_eq!(candidates[0].1, candidates[1].1, "test 24 8");
 
-        let test = dec_to_bibi.swap("2000").unwrap();
-        assert_eq!(test, "BIDAHO", "test 2 1");
+        // all numbers are read
+        let candidates = registry.rank(&["101", "2"]);
+        assert_eq!(candidates[0].0, "oct", "test 24 9");
+        let candidates = registry.rank(&["17VZNX1SN5NtKa8UQFxwQbFeFc3iqRYhem"]);
+        assert_eq!(candidates.len(), 1, "test 24 10");
+        assert_eq!(candidates[0].0, "base58", "test 24 11");
 
-        //let bibi = NumeralSystem::new("", "", vec!(vec!("H", "B", "K", "D"), vec!("O", "A", "E", "I"))).unwrap();
-        let test = dec_to_bibi.swap("2000").unwrap();
-        assert_eq!(test, "BIDAHO", "test 2 2");
+        // prefixes are free
+        let candidates = registry.rank(&["0b101"]);
+        assert_eq!(candidates[0].0, "bin", "test 24 12");
+        assert!(candidates[0].1 > 0.99, "test 24 13");
 
-        let bin = NumeralSystem::new_from_tag("bin").unwrap();
-        let hex = NumeralSystem::new_from_tag("hex").unwrap();
-        let dec = NumeralSystem::new_from_tag("dec").unwrap();
+        // codecs which are not numeral systems
+        let candidates = registry.rank(&["MMXIX"]);
+        assert_eq!(candidates[0].0, "roman", "test 24 14");
+        assert_eq!(candidates[1].0, "roman-vinculum", "test 24 15");
+        assert_eq!(candidates[2].0, "excel", "test 24 16");
+        let candidates = registry.rank(&["deux mille dix-neuf"]);
+        assert_eq!(candidates[0].0, "words-fr", "test 24 17");
 
-        let hex_to_dec = BibiCoder::new(hex, dec);
+        assert!(registry.rank(&["0é"]).is_empty(), "test 24 18");
+        let acute = NumeralSystem::new("", vec![vec!["é"], vec!["0", "1"]]).unwrap();
+        registry.register("acute", acute).unwrap();
+        assert_eq!(registry.rank(&["é0"])[0].0, "acute", "test 24 19");
+    }
+
+    #[test]
+    fn test_limbs() {
+        // digits read and written one by one
+        let naive_from = |digits: &[u32], radix: u32| {
+            let mut limbs = vec![];
+            for digit in digits {
+                limbs_mul_small(&mut limbs, radix);
+                limbs_add_small(&mut limbs, *digit);
+            }
+            limbs
+        };
+        let mut seed: u64 = 42;
+        for radix in [2, 3, 10, 16, 58, 256, 65536, 70000] {
+            for len in [1, 5, 9, 10, 18, 19, 33, 100, 1000] {
+                let digits: Vec<u32> = (0..len)
+                    .map(|idx| {
+                        seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+                        if idx == 0 {
+                            1 + (seed >> 33) as u32 % (radix - 1)
+                        } else {
+                            (seed >> 33) as u32 % radix
+                        }
+                    })
+                    .collect();
+                let limbs = limbs_from_digits(digits.iter().copied(), radix);
+                assert_eq!(
+                    limbs,
+                    naive_from(&digits, radix),
+                    "test 25 1 {} {}",
+                    radix,
+                    len
+                );
+                let mut back = limbs_to_digits(&limbs, radix);
+                back.reverse();
+                assert_eq!(back, digits, "test 25 2 {} {}", radix, len);
+            }
+        }
+        assert!(
+            limbs_from_digits(vec![0, 0, 0].into_iter(), 10).is_empty(),
+            "test 25 3"
+        );
+        assert!(limbs_to_digits(&[], 10).is_empty(), "test 25 4");
 
         let dec = NumeralSystem::new_from_tag("dec").unwrap();
         let hex = NumeralSystem::new_from_tag("hex").unwrap();
-        let dec_to_hex = BibiCoder::new(dec, hex);
+        let dec_to_hex = BibiCoder::new(dec.clone(), hex.clone());
+        let hex_to_dec = BibiCoder::new(hex, dec);
+        assert_eq!(
+            dec_to_hex.swap("1000000000").unwrap(),
+            "0x3b9aca00",
+            "test 25 5"
+        );
+        assert_eq!(
+            hex_to_dec.swap(&format!("0x1{}", "0".repeat(32))).unwrap(),
+            "340282366920938463463374607431768211456",
+            "test 25 6"
+        );
+        let large = format!("9{}", "0123456789".repeat(300));
+        let swapped = dec_to_hex.swap(&large).unwrap();
+        assert_eq!(hex_to_dec.swap(&swapped).unwrap(), large, "test 25 7");
+    }
 
-        let hex = NumeralSystem::new_from_tag("hex").unwrap();
-        let hex_to_bin = BibiCoder::new(hex, bin);
+    #[test]
+    fn test_huge() {
+        let mut seed: u64 = 7;
+        let mut random = |len: usize| -> Vec<u32> {
+            let mut limbs: Vec<u32> = (0..len)
+                .map(|_| {
+                    seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+                    (seed >> 32) as u32
+                })
+                .collect();
+            limbs_trim(&mut limbs);
+            limbs
+        };
 
-        let test = hex_to_dec.swap("ffff").unwrap();
-        assert_eq!(test, "65535", "test 2 3");
+        for (len_a, len_b) in [(40, 40), (100, 33), (257, 129), (300, 31), (500, 120)] {
+            let (a, b) = (random(len_a), random(len_b));
+            assert_eq!(huge::mul(&a, &b), limbs_mul(&a, &b), "test 26 1 {}", len_a);
+        }
+        for (len_n, len_d) in [
+            (10, 3),
+            (120, 50),
+            (200, 99),
+            (401, 200),
+            (700, 64),
+            (300, 150),
+        ] {
+            let (n, mut d) = (random(len_n), random(len_d));
+            // small high limbs give large corrections of the quotient
+            d[len_d - 1] = 1;
+            let (quotient, rem) = huge::divmod(&n, &d);
+            assert_eq!(limbs_cmp(&rem, &d), Ordering::Less, "test 26 2 {}", len_n);
+            let mut back = limbs_mul(&quotient, &d);
+            limbs_add(&mut back, &rem);
+            limbs_trim(&mut back);
+            assert_eq!(back, n, "test 26 3 {}", len_n);
+        }
+        let (quotient, rem) = huge::divmod(&[5], &random(60));
+        assert!(quotient.is_empty() && rem == [5], "test 26 4");
 
-        let test = hex_to_bin.swap("f0ff").unwrap();
-        assert_eq!(test, "0b1111000011111111", "test 2 4");
+        // identical to schoolbook conversions
+        for radix in [2, 3, 10, 58, 70000] {
+            for len in [300, 1000, 2000] {
+                let limbs = random(len);
+                let digits = huge::to_digits(&limbs, radix);
+                assert_eq!(
+                    digits,
+                    limbs_to_digits(&limbs, radix),
+                    "test 26 5 {}",
+                    radix
+                );
+                let mut digits = digits;
+                digits.reverse();
+                // leading zero digits
+                digits.splice(0..0, vec![0; 50]);
+                let back = huge::from_digits(&digits, radix);
+                assert_eq!(back, limbs, "test 26 6 {}", radix);
+                assert_eq!(
+                    back,
+                    limbs_from_digits(digits.iter().copied(), radix),
+                    "test 26 7 {}",
+                    radix
+                );
+            }
+        }
+        // powers of the radix and numbers made of zeros
+        let power = huge::from_digits(&[vec![1], vec![0; 10000]].concat(), 10);
+        let mut digits = huge::to_digits(&power, 10);
+        digits.reverse();
+        assert_eq!(digits, [vec![1], vec![0; 10000]].concat(), "test 26 8");
+        assert!(huge::from_digits(&[0; 5000], 10).is_empty(), "test 26 9");
 
-        let test = dec_to_hex
-            .swap("324439924324324235436544328757654635345424324543")
-            .unwrap();
+        let dec = NumeralSystem::new_from_tag("dec").unwrap();
+        let bibi = NumeralSystem::new_from_tag("bibi").unwrap();
+        let dec_to_bibi = BibiCoder::new(dec.clone(), bibi.clone());
+        let bibi_to_dec = BibiCoder::new(bibi, dec);
+        let large = format!("1{}", "9876543210".repeat(1500));
+        let swapped = dec_to_bibi.swap(&large).unwrap();
+        assert_eq!(bibi_to_dec.swap(&swapped).unwrap(), large, "test 26 10");
+    }
+
+    #[test]
+    fn test_regrouping() {
+        let mut seed: u64 = 3;
+        let mut random = |len: usize, radix: u32| -> Vec<u32> {
+            (0..len)
+                .map(|_| {
+                    seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+                    (seed >> 33) as u32 % radix
+                })
+                .collect()
+        };
+        for bits in [1, 2, 3, 4, 5, 8, 20, 31] {
+            let radix = 1 << bits;
+            for len in [0, 1, 7, 32, 33, 100] {
+                let digits = random(len, radix);
+                let limbs = limbs_from_groups(&digits, bits);
+                assert_eq!(
+                    limbs,
+                    limbs_from_digits(digits.iter().copied(), radix),
+                    "test 27 1 {} {}",
+                    bits,
+                    len
+                );
+                assert_eq!(
+                    limbs_to_groups(&limbs, bits),
+                    limbs_to_digits(&limbs, radix),
+                    "test 27 2 {} {}",
+                    bits,
+                    len
+                );
+            }
+        }
 
+        // fractions regrouped as they are multiplied digit by digit
+        for (fraction_bits, bits) in [(1, 4), (4, 1), (4, 3), (3, 5), (8, 4), (5, 5)] {
+            for precision in [0, 1, 2, 5, 20] {
+                for len in [1, 2, 7] {
+                    let mut fraction = random(len, 1 << fraction_bits);
+                    let regrouped = regroup_fraction(&fraction, fraction_bits, bits, precision);
+                    let mut digits = vec![];
+                    while digits.len() < precision && fraction.iter().any(|digit| *digit > 0) {
+                        digits.push(mul_fraction(&mut fraction, 1 << fraction_bits, 1 << bits));
+                    }
+                    let remainder = if fraction.iter().any(|digit| *digit > 0) {
+                        let half = mul_fraction(&mut fraction, 1 << fraction_bits, 2);
+                        let rest = fraction.iter().any(|digit| *digit > 0);
+                        Some(match (half, rest) {
+                            (0, _) => Ordering::Less,
+                            (_, true) => Ordering::Greater,
+                            _ => Ordering::Equal,
+                        })
+                    } else {
+                        None
+                    };
+                    assert_eq!(
+                        regrouped,
+                        (digits, remainder),
+                        "test 27 3 {} {} {}",
+                        fraction_bits,
+                        bits,
+                        precision
+                    );
+                }
+            }
+        }
+
+        let hex = NumeralSystem::new_from_tag("hex").unwrap();
+        let oct = NumeralSystem::new_from_tag("oct").unwrap();
+        let bibi = NumeralSystem::new_from_tag("bibi").unwrap();
+        let dec = NumeralSystem::new_from_tag("dec").unwrap();
+        let mut hex_to_oct = BibiCoder::new(hex.clone(), oct.clone());
+        assert_eq!(hex_to_oct.get_regrouping(), Some((4, 3)), "test 27 4");
+        let bibi_to_bin = BibiCoder::new(bibi, NumeralSystem::new_from_tag("bin").unwrap());
+        assert_eq!(bibi_to_bin.get_regrouping(), Some((4, 1)), "test 27 5");
         assert_eq!(
-            test, "0x38d463ad8fa67a74d6e9a610158623c60d2297bf",
-            "test 2 5"
+            BibiCoder::new(dec.clone(), hex.clone()).get_regrouping(),
+            None,
+            "test 27 6"
+        );
+        assert_eq!(
+            BibiCoder::new(hex.clone(), Roman::default()).get_regrouping(),
+            None,
+            "test 27 7"
         );
 
-        let test = dec_to_hex.swap("45641230").unwrap();
-        assert_eq!(test, "0x2b86e0e", "test 2 6");
+        assert_eq!(
+            bibi_to_bin.swap("HAHOBI").unwrap(),
+            "0b100000111",
+            "test 27 8"
+        );
+        assert_eq!(
+            hex_to_oct.swap("-0xfff.fff").unwrap(),
+            "-0o7777.7777",
+            "test 27 9"
+        );
+        hex_to_oct.set_precision(3);
+        assert_eq!(
+            hex_to_oct.swap("0xfff.fff").unwrap(),
+            "0o7777.777",
+            "test 27 10"
+        );
+        hex_to_oct.set_rounding(Rounding::HalfEven);
+        assert_eq!(
+            hex_to_oct.swap("0xfff.fff").unwrap(),
+            "0o10000",
+            "test 27 11"
+        );
+        assert_eq!(hex_to_oct.swap("0x0.004").unwrap(), "0o0", "test 27 12");
+        assert_eq!(hex_to_oct.swap("0x0.00c").unwrap(), "0o0.002", "test 27 13");
 
-        let test = hex_to_dec
-            .swap("38d463ad8fa67a74d6e9a610158623c60d2297bf")
+        let large = format!("1{}", "0123456789abcdef".repeat(5000));
+        let swapped = BibiCoder::new(hex.clone(), oct.clone())
+            .swap(&large)
             .unwrap();
+        let back = BibiCoder::new(oct, hex).swap(&swapped).unwrap();
+        assert_eq!(back, format!("0x{}", large), "test 27 14");
+    }
 
-        assert_eq!(
-            test, "324439924324324235436544328757654635345424324543",
-            "test 2 7"
-        );
+    #[test]
+    fn test_stream() {
+        // input given a few bytes at a time
+        struct Trickle<'a>(&'a [u8], usize);
+        impl<'a> Read for Trickle<'a> {
+            fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+                let len = self.1.min(self.0.len()).min(buf.len());
+                buf[..len].copy_from_slice(&self.0[..len]);
+                self.0 = &self.0[len..];
+                Ok(len)
+            }
+        }
+        let stream = |coder: &BibiCoder, entry: &str, step: usize| {
+            let mut output = vec![];
+            coder
+                .swap_stream(Trickle(entry.as_bytes(), step), &mut output)
+                .map(|_| String::from_utf8(output).unwrap())
+        };
 
-        let sys = NumeralSystem::new(
+        let base32: Vec<String> = "abcdefghijklmnopqrstuvwxyz234567"
+            .chars()
+            .map(|c| c.to_string())
+            .collect();
+        let base32 =
+            NumeralSystem::new("", vec![base32.iter().map(|digit| &digit[..]).collect()]).unwrap();
+        let hexa: Vec<String> = (0..16).map(|digit| digit.to_string()).collect();
+        let hexa = NumeralSystem::new_with_separator(
             "",
-            vec![
-                vec![
-                    "B", "K", "D", "F", "G", "J", "L", "M", "N", "P", "R", "S", "T", "V", "X", "Z",
-                ],
-                vec!["a-", "i-", "o-", "u-"],
-            ],
+            ":",
+            vec![hexa.iter().map(|digit| &digit[..]).collect()],
         )
         .unwrap();
+        let arabic: Vec<String> = "٠١٢٣٤٥٦٧٨٩".chars().map(|c| c.to_string()).collect();
+        let arabic =
+            NumeralSystem::new("", vec![arabic.iter().map(|digit| &digit[..]).collect()]).unwrap();
+        let mut systems = vec![base32, hexa];
+        for tag in &["hex", "bin", "oct", "bibi"] {
+            systems.push(NumeralSystem::new_from_tag(tag).unwrap());
+        }
+        let mut pairs: Vec<(NumeralSystem, NumeralSystem)> = vec![];
+        for num_in in systems.iter() {
+            for num_out in systems.iter() {
+                pairs.push((num_in.clone(), num_out.clone()));
+            }
+        }
+        let dec = NumeralSystem::new_from_tag("dec").unwrap();
+        pairs.push((dec.clone(), arabic.clone()));
+        pairs.push((arabic, dec.clone()));
 
-        let hex = NumeralSystem::new_from_tag("hex").unwrap();
-        let hex_to_sys = BibiCoder::new(hex, sys);
-        let test = hex_to_sys
-            .swap("de0b295669a9fd93d5f28d9ec85e40f4cb697bae")
-            .unwrap();
-
-        let test2 = hex_to_sys
-            .swap("0xde0b295669a9fd93d5f28d9ec85e40f4cb697bae")
-            .unwrap();
+        let numbers = [
+            "0",
+            "000",
+            "1",
+            "-5",
+            "-0",
+            "255",
+            "256",
+            "00065535",
+            "123456789012345678901234567890",
+            "0.5",
+            "-0.0625",
+            "-1.75",
+            "000.00390625",
+            "255.1",
+            "1234.00000762939453125",
+        ];
+        for (num_in, num_out) in pairs {
+            let mut preserve = BibiCoder::new(dec.clone(), num_in.clone());
+            preserve.set_leading_zeros(LeadingZeros::Preserve);
+            preserve.set_precision(30);
+            let mut coder = BibiCoder::new(num_in, num_out);
+            assert!(coder.can_stream(), "test 28 1");
+            for number in numbers.iter() {
+                let entry = preserve.swap(number).unwrap();
+                // line breaks anywhere
+                let broken: String = entry
+                    .chars()
+                    .enumerate()
+                    .flat_map(|(idx, c)| {
+                        let mut ret = vec![c];
+                        if idx % 3 == 1 {
+                            ret.push('\n');
+                        }
+                        ret
+                    })
+                    .collect();
+                for leading_zeros in [LeadingZeros::Drop, LeadingZeros::Preserve] {
+                    coder.set_leading_zeros(leading_zeros);
+                    for step in [1, 2, 3, 1000] {
+                        coder.set_precision(step % 7);
+                        assert_eq!(
+                            stream(&coder, &entry, step).unwrap(),
+                            coder.swap(&entry).unwrap(),
+                            "test 28 2 {} {}",
+                            entry,
+                            step
+                        );
+                    }
+                    coder.set_precision(20);
+                    let swapped = coder.swap(&entry).unwrap();
+                    assert_eq!(
+                        stream(&coder, &broken, 1).unwrap(),
+                        swapped,
+                        "test 28 3 {}",
+                        entry
+                    );
+                }
+            }
+        }
 
-        assert_eq!(
-            test,
-            "Fi-Xa-Du-Do-Ji-Li-Ri-Ro-Mu-Vo-Gu-Vi-Mu-Do-Fi-Pu-Sa-Ni-Mo-Ga-Fu-Gu-Du-Lo-Ju-So-So-",
-            "test 2 8"
+        let hex = NumeralSystem::new_from_tag("hex").unwrap();
+        let bibi = NumeralSystem::new_from_tag("bibi").unwrap();
+        let mut integers = bibi.clone();
+        integers.set_radix_point("").unwrap();
+        let mut positive = bibi.clone();
+        positive.set_signs(vec![], vec![]).unwrap();
+        for (num_out, entry) in [(integers, "0x1.8"), (positive, "-0x0.8")] {
+            let coder = BibiCoder::new(hex.clone(), num_out);
+            let error = coder.swap(entry).unwrap_err();
+            let streamed = stream(&coder, entry, 10).unwrap_err();
+            assert_eq!(
+                format!("{:?}", streamed),
+                format!("{:?}", error),
+                "test 28 4 {}",
+                entry
+            );
+        }
+        let mut hex_to_bibi = BibiCoder::new(hex.clone(), bibi.clone());
+        for entry in ["", "\n", "-", "0x", "0xfg", "0x\u{e9}", ".", "1.2.3"] {
+            assert!(
+                matches!(
+                    stream(&hex_to_bibi, entry, 10),
+                    Err(BibiError::EntryMismatchWithNumeralSystem)
+                ),
+                "test 28 5 {}",
+                entry
+            );
+        }
+        assert!(
+            matches!(
+                hex_to_bibi.swap_stream(&[0xff, 0xfe][..], vec![]),
+                Err(BibiError::EntryMismatchWithNumeralSystem)
+            ),
+            "test 28 6"
         );
-        assert_eq!(
-            test2,
-            "Fi-Xa-Du-Do-Ji-Li-Ri-Ro-Mu-Vo-Gu-Vi-Mu-Do-Fi-Pu-Sa-Ni-Mo-Ga-Fu-Gu-Du-Lo-Ju-So-So-",
-            "test 2 9"
+        hex_to_bibi.set_leading_zeros(LeadingZeros::ByteAligned);
+        assert!(!hex_to_bibi.can_stream(), "test 28 7");
+        hex_to_bibi.set_leading_zeros(LeadingZeros::Drop);
+        hex_to_bibi.set_rounding(Rounding::HalfEven);
+        assert!(!hex_to_bibi.can_stream(), "test 28 15");
+        hex_to_bibi.set_rounding(Rounding::Truncate);
+        hex_to_bibi.set_signed(Some((SignedEncoding::TwosComplement, 8)));
+        assert!(
+            matches!(
+                stream(&hex_to_bibi, "ff", 10),
+                Err(BibiError::NotStreamable)
+            ),
+            "test 28 8"
+        );
+        let excel = NumeralSystem::new_from_tag("excel").unwrap();
+        assert!(
+            !BibiCoder::new(dec.clone(), hex.clone()).can_stream(),
+            "test 28 9"
+        );
+        assert!(
+            !BibiCoder::new(hex.clone(), excel).can_stream(),
+            "test 28 10"
+        );
+        // 0 and 01 are uniquely decodable, but 0 is the beginning of 01
+        let suffix = NumeralSystem::new("", vec![vec!["0", "01"]]).unwrap();
+        assert!(
+            !BibiCoder::new(suffix.clone(), hex.clone()).can_stream(),
+            "test 28 11"
+        );
+        assert!(
+            BibiCoder::new(hex.clone(), suffix).can_stream(),
+            "test 28 12"
+        );
+        assert!(
+            !BibiCoder::new(hex.clone(), Roman::default()).can_stream(),
+            "test 28 13"
         );
 
-        let sys = NumeralSystem::new(
+        let large = format!("0x1{}", "0123456789abcdef".repeat(5000));
+        let to_bibi = BibiCoder::new(hex.clone(), bibi);
+        let to_bin = BibiCoder::new(hex.clone(), NumeralSystem::new_from_tag("bin").unwrap());
+        for coder in [to_bibi, to_bin] {
+            assert_eq!(
+                stream(&coder, &large, 100_000).unwrap(),
+                coder.swap(&large).unwrap(),
+                "test 28 14"
+            );
+        }
+    }
+
+    #[test]
+    fn test_u128() {
+        let base32: Vec<String> = "abcdefghijklmnopqrstuvwxyz234567"
+            .chars()
+            .map(|c| c.to_string())
+            .collect();
+        let base32 =
+            NumeralSystem::new("", vec![base32.iter().map(|digit| &digit[..]).collect()]).unwrap();
+        let hexa: Vec<String> = (0..16).map(|digit| digit.to_string()).collect();
+        let hexa = NumeralSystem::new_with_separator(
+            "",
+            ":",
+            vec![hexa.iter().map(|digit| &digit[..]).collect()],
+        )
+        .unwrap();
+        let arabic: Vec<String> = "٠١٢٣٤٥٦٧٨٩".chars().map(|c| c.to_string()).collect();
+        let arabic =
+            NumeralSystem::new("", vec![arabic.iter().map(|digit| &digit[..]).collect()]).unwrap();
+        let budu = NumeralSystem::new(
             "",
             vec![
                 vec![
                     "B", "K", "D", "F", "G", "J", "L", "M", "N", "P", "R", "S", "T", "V", "X", "Z",
                 ],
-                vec!["a-", "i-", "o-", "u-"],
+                vec!["a", "i", "o", "u"],
             ],
         )
         .unwrap();
-        let hex = NumeralSystem::new_from_tag("hex").unwrap();
-        let sys_to_hex = BibiCoder::new(sys, hex);
-        let test = sys_to_hex
-            .swap(
-                "Fi-Xa-Du-Do-Ji-Li-Ri-Ro-Mu-Vo-Gu-Vi-Mu-Do-Fi-Pu-Sa-Ni-Mo-Ga-Fu-Gu-Du-Lo-Ju-So-So-",
-            )
-            .unwrap();
-
-        assert_eq!(
-            test, "0xde0b295669a9fd93d5f28d9ec85e40f4cb697bae",
-            "test 2 10"
-        );
-
-    }
+        let mut unsigned = NumeralSystem::new_from_tag("hex").unwrap();
+        unsigned.set_signs(vec![], vec![]).unwrap();
+        let mut negabinary = NumeralSystem::new_from_tag("bin").unwrap();
+        negabinary.set_negative_base(true).unwrap();
+        // uniquely decodable, but not prefix-free
+        let suffix = NumeralSystem::new("", vec![vec!["0", "01"]]).unwrap();
+        let mut systems = vec![base32, hexa, arabic, budu, unsigned, negabinary, suffix];
+        for tag in &["dec", "hex", "bin", "oct", "bibi"] {
+            systems.push(NumeralSystem::new_from_tag(tag).unwrap());
+        }
 
-    #[test]
-    fn test_errorok() {
+        let numbers = [
+            "0",
+            "-0",
+            "000",
+            "1",
+            "-5",
+            "+7",
+            "00065535",
+            "18446744073709551615",
+            "18446744073709551616",
+            "-170141183460469231731687303715884105728",
+            "340282366920938463463374607431768211455",
+            "0340282366920938463463374607431768211455",
+            "340282366920938463463374607431768211456",
+            "123456789012345678901234567890123456789012345678901234567890",
+        ];
         let dec = NumeralSystem::new_from_tag("dec").unwrap();
-        let bibi = NumeralSystem::new_from_tag("bibi").unwrap();
-        let bibi_to_dec: BibiCoder = BibiCoder::new(bibi, dec);
-
-        let test = bibi_to_dec.swap("0");
-        assert!(test.is_err());
+        for num_in in systems.iter() {
+            let mut entries: Vec<String> = ["", "-", "1 2", "1.5", "٣x", "0x"]
+                .iter()
+                .map(|entry| entry.to_string())
+                .collect();
+            let mut preserve = BibiCoder::new(dec.clone(), num_in.clone());
+            preserve.set_leading_zeros(LeadingZeros::Preserve);
+            entries.extend(
+                numbers
+                    .iter()
+                    .filter_map(|number| preserve.swap(number).ok()),
+            );
+            for num_out in systems.iter() {
+                for leading_zeros in [LeadingZeros::Drop, LeadingZeros::Preserve] {
+                    let mut coder = BibiCoder::new(num_in.clone(), num_out.clone());
+                    coder.set_leading_zeros(leading_zeros);
+                    for entry in entries.iter() {
+                        // as written through the pivot
+                        let expected = num_in.to_pivot(entry).and_then(|pivot| {
+                            num_out.write_pivot(&pivot, leading_zeros, 20, Rounding::Truncate, 0)
+                        });
+                        assert_eq!(
+                            format!("{:?}", coder.swap(entry)),
+                            format!("{:?}", expected.map(|(number, _)| number)),
+                            "test 29 1 {} {} {}",
+                            num_in,
+                            num_out,
+                            entry
+                        );
+                    }
+                }
+            }
+        }
 
-        let test = bibi_to_dec.swap("");
-        assert!(test.is_err());
+        let hex = NumeralSystem::new_from_tag("hex").unwrap();
+        let mut coder = BibiCoder::new(dec.clone(), hex.clone());
+        assert_eq!(
+            coder.swap_u128("340282366920938463463374607431768211455"),
+            Some(String::from("0xffffffffffffffffffffffffffffffff")),
+            "test 29 2"
+        );
+        assert_eq!(
+            coder.swap_u128("340282366920938463463374607431768211456"),
+            None,
+            "test 29 3"
+        );
+        assert_eq!(coder.swap_u128("1.5"), None, "test 29 4");
+        assert_eq!(
+            coder.swap_u128("-0"),
+            Some(String::from("0x0")),
+            "test 29 5"
+        );
+        coder.set_leading_zeros(LeadingZeros::Preserve);
+        assert_eq!(
+            coder.swap_u128("-0012"),
+            Some(String::from("-0x00c")),
+            "test 29 6"
+        );
+        coder.set_leading_zeros(LeadingZeros::ByteAligned);
+        assert_eq!(coder.swap_u128("12"), None, "test 29 7");
+        assert_eq!(
+            BibiCoder::new(dec.clone(), Roman::default()).swap_u128("12"),
+            None,
+            "test 29 8"
+        );
     }
 
     #[test]
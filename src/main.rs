@@ -1,26 +1,26 @@
 #[macro_use]
-
 extern crate clap;
 use clap::App;
 
 //use std::process;
 use std::fs;
 use std::fs::File;
+use std::io;
+use std::io::{BufRead, Read, Write};
 use std::path::Path;
-//use std::io;
+use std::rc::Rc;
 
 extern crate bibicode;
-use bibicode::{BibiCoder, BibiError, NumeralSystem};
+use bibicode::{
+    BibiCoder, BibiError, BitPatterns, Codec, LeadingZeros, NumeralSystem, Registry, Roman,
+    Rounding, SignedEncoding, Words,
+};
 
 extern crate xdg;
 
 extern crate serde_derive;
 use serde_derive::{Deserialize, Serialize};
 
-extern crate indexmap;
-use indexmap::map::IndexMap;
-use std::collections::HashMap;
-
 // get numeral system from file
 // the file is a json description of a numeral system
 // example :
@@ -31,11 +31,62 @@ use std::collections::HashMap;
 // digits can be the combination of any arrays
 // example :
 // {  "digits":[["H", "B", "K", "D"],["O", "A", "E", "I"]] }
+// signs written before negative or positive numbers can be given
+// example :
+// {  "minus":["n"], "plus":["p"], "digits":["-", "|"] }
+// a separator can be written between digits
+// example :
+// {  "separator":"-", "digits":[["H", "B", "K", "D"],["O", "A", "E", "I"]] }
+// the radix point written before the fractional part can be given (empty for integers only)
+// example :
+// {  "radix_point":",", "digits":["0", "1"] }
+// the fraction bar written between numerator and denominator of rational numbers can be given
+// example :
+// {  "fraction_bar":":", "digits":["0", "1"] }
+// numeration can be bijective, digits standing for 1..radix
+// example :
+// {  "bijective":true, "digits":["A", "B", "C"] }
+// the radix can be negative (-len of digits)
+// example :
+// {  "negative_base":true, "digits":["0", "1", "2"] }
+// digits can have explicit values, the radix being the number of digits if not given
+// example :
+// {  "values":[-1, 0, 1], "digits":["-", "0", "+"] }
+// {  "values":[0, 1, 2], "radix":2, "digits":["0", "1", "2"] }
+// mixed-radix systems give the digits of each position, from the most significant one, and the
+// numeral system (tag) of what is left after the positions
+// example :
+// {  "separator":":", "positions":[["0", "1", "2", "3", "4", "5", "6"], ["00", "01", ..., "23"]], "head":"dec" }
+// positions can also be given by their radices, digits being decimal numbers
+// example :
+// {  "separator":":", "radices":[7, 24, 60] }
+// unary systems (one digit) can write a marker for each group of five marks, and limit the number
+// of marks written
+// example :
+// {  "unary_group":"卌", "length_limit":1000, "digits":["|"] }
 fn num_from_path(path: &str) -> Result<NumeralSystem, BibiError> {
     #[derive(Serialize, Deserialize, Debug)]
     pub struct FakeNumeralSystem {
         #[serde(default)]
         prefix: String,
+        #[serde(default)]
+        separator: String,
+        minus: Option<Vec<String>>,
+        plus: Option<Vec<String>>,
+        radix_point: Option<String>,
+        fraction_bar: Option<String>,
+        #[serde(default)]
+        bijective: bool,
+        #[serde(default)]
+        negative_base: bool,
+        values: Option<Vec<i32>>,
+        radix: Option<usize>,
+        unary_group: Option<String>,
+        length_limit: Option<usize>,
+        positions: Option<Vec<Vec<String>>>,
+        radices: Option<Vec<usize>>,
+        head: Option<String>,
+        #[serde(default)]
         digits: Vec<Vec<String>>,
     }
 
@@ -43,6 +94,20 @@ fn num_from_path(path: &str) -> Result<NumeralSystem, BibiError> {
     pub struct FakeNumeralSystem2 {
         #[serde(default)]
         prefix: String,
+        #[serde(default)]
+        separator: String,
+        minus: Option<Vec<String>>,
+        plus: Option<Vec<String>>,
+        radix_point: Option<String>,
+        fraction_bar: Option<String>,
+        #[serde(default)]
+        bijective: bool,
+        #[serde(default)]
+        negative_base: bool,
+        values: Option<Vec<i32>>,
+        radix: Option<usize>,
+        unary_group: Option<String>,
+        length_limit: Option<usize>,
         digits: Vec<String>,
     }
 
@@ -57,125 +122,321 @@ fn num_from_path(path: &str) -> Result<NumeralSystem, BibiError> {
         }
     };
 
-    let test: Result<FakeNumeralSystem, _> = serde_json::from_str(&contents);
-    match test {
-        Ok(fakenum) => return NumeralSystem::new_from_strings(fakenum.prefix, fakenum.digits),
-        Err(_) => {
-            let test: Result<FakeNumeralSystem2, _> = serde_json::from_str(&contents);
-            match test {
-                Ok(fakenum) => {
-                    return NumeralSystem::new_from_strings(fakenum.prefix, vec![fakenum.digits])
-                }
-                Err(_) => return Err(BibiError::BadNumeralSystem),
-            }
+    let fakenum: FakeNumeralSystem = match serde_json::from_str(&contents) {
+        Ok(fakenum) => fakenum,
+        Err(_) => match serde_json::from_str::<FakeNumeralSystem2>(&contents) {
+            Ok(fakenum) => FakeNumeralSystem {
+                prefix: fakenum.prefix,
+                separator: fakenum.separator,
+                minus: fakenum.minus,
+                plus: fakenum.plus,
+                radix_point: fakenum.radix_point,
+                fraction_bar: fakenum.fraction_bar,
+                bijective: fakenum.bijective,
+                negative_base: fakenum.negative_base,
+                values: fakenum.values,
+                radix: fakenum.radix,
+                unary_group: fakenum.unary_group,
+                length_limit: fakenum.length_limit,
+                positions: None,
+                radices: None,
+                head: None,
+                digits: vec![fakenum.digits],
+            },
+            Err(_) => return Err(BibiError::BadNumeralSystem),
+        },
+    };
+
+    let positions = match (fakenum.positions, fakenum.radices) {
+        (Some(positions), _) => Some(positions),
+        (None, Some(radices)) => Some(
+            radices
+                .iter()
+                .map(|radix| {
+                    let width = radix.saturating_sub(1).to_string().len();
+                    (0..*radix)
+                        .map(|digit| format!("{:0width$}", digit, width = width))
+                        .collect()
+                })
+                .collect(),
+        ),
+        (None, None) => None,
+    };
+    let mut num = match positions {
+        Some(positions) => {
+            let head = match fakenum.head {
+                Some(tag) => Some(NumeralSystem::new_from_tag(&tag)?),
+                None => None,
+            };
+            NumeralSystem::new_mixed(
+                &fakenum.prefix,
+                &fakenum.separator,
+                positions
+                    .iter()
+                    .map(|position| position.iter().map(|digit| &digit[..]).collect())
+                    .collect(),
+                head,
+            )?
         }
+        None => NumeralSystem::new_from_strings_with_separator(
+            fakenum.prefix,
+            fakenum.separator,
+            fakenum.digits,
+        )?,
     };
+    if fakenum.minus.is_some() || fakenum.plus.is_some() {
+        let (minus, plus) = num.get_signs();
+        let minus = fakenum.minus.unwrap_or(minus);
+        let plus = fakenum.plus.unwrap_or(plus);
+        num.set_signs(
+            minus.iter().map(|sign| &sign[..]).collect(),
+            plus.iter().map(|sign| &sign[..]).collect(),
+        )?;
+    }
+    if fakenum.bijective {
+        num.set_bijective(true)?;
+    }
+    if fakenum.negative_base {
+        num.set_negative_base(true)?;
+    }
+    if let Some(marker) = fakenum.unary_group {
+        num.set_unary_group(&marker)?;
+    }
+    if let Some(length_limit) = fakenum.length_limit {
+        num.set_length_limit(length_limit);
+    }
+    if let Some(values) = fakenum.values {
+        let radix = fakenum.radix.unwrap_or(values.len());
+        num.set_values(values, radix)?;
+    }
+    if let Some(radix_point) = fakenum.radix_point {
+        num.set_radix_point(&radix_point)?;
+    }
+    if let Some(fraction_bar) = fakenum.fraction_bar {
+        num.set_fraction_bar(&fraction_bar)?;
+    }
+    Ok(num)
 }
 
-// extract prefix from a file (file = description of numeral system)
-// this function is used to get the prefix of numeral systems without
-// instantiate the numeral system
-fn extract_prefix_from_path(path: &str) -> Option<String> {
-    if File::open(path).is_err() {
-        return None;
+// codecs which are not numeral systems read leniently : Roman numerals and words
+fn lenient_codec(tag: &str) -> Option<Rc<dyn Codec>> {
+    if let Ok(mut roman) = Roman::from_tag(tag) {
+        roman.set_lenient(true);
+        return Some(Rc::new(roman));
     }
-    let contents = match fs::read_to_string(path) {
-        Ok(contents) => contents,
-        Err(_) => {
-            return None;
-        }
-    };
-    let test: serde_json::Value = serde_json::from_str(&contents).unwrap();
-    if !test["prefix"].is_null() {
-        return Some(test["prefix"].as_str().unwrap().to_string());
+    if let Ok(mut words) = Words::from_tag(tag) {
+        words.set_lenient(true);
+        return Some(Rc::new(words));
     }
     None
 }
 
+// minimal confidence in the numbers read by the codec found out with --from auto
+const AUTO_CONFIDENCE: f64 = 0.9;
+
+// find out the codec of numbers without prefix : the most likely codec is chosen if the numbers it
+// reads are likely enough (read the same way by other candidates), otherwise the candidates are listed
+fn autodetect_codec(registry: &Registry, numbers: &[&str]) -> Result<Rc<dyn Codec>, BibiError> {
+    let candidates = registry.rank(numbers);
+    // numbers read by a codec, written in decimal
+    let dec = NumeralSystem::new_from_tag("dec")?;
+    let reads = |name: &str| -> Vec<Option<String>> {
+        let codec = registry.get(name).unwrap();
+        numbers
+            .iter()
+            .map(|number| dec.from_pivot(&codec.to_pivot(number).ok()?).ok())
+            .collect()
+    };
+    let best = match candidates.first() {
+        Some((name, _)) => *name,
+        None => return Err(BibiError::EntryMismatchWithNumeralSystem),
+    };
+    let read = reads(best);
+    let confidence: f64 = candidates
+        .iter()
+        .filter(|(name, _)| reads(name) == read)
+        .map(|(_, confidence)| confidence)
+        .sum();
+    if confidence >= AUTO_CONFIDENCE {
+        eprintln!("from : {} ({:.0} %)", best, confidence * 100.0);
+        return Ok(registry.get(best).unwrap());
+    }
+    eprintln!("ambiguous numeral system, candidates :");
+    for (name, confidence) in candidates
+        .iter()
+        .filter(|(_, confidence)| *confidence >= 0.01)
+    {
+        eprintln!("  {} ({:.0} %)", name, confidence * 100.0);
+    }
+    let names = candidates
+        .iter()
+        .map(|(name, _)| name.to_string())
+        .collect();
+    Err(BibiError::AmbiguousNumeralSystem(names))
+}
+
 fn main() -> Result<(), BibiError> {
     let yaml = load_yaml!("bibic.yaml");
     let matches = App::from_yaml(yaml).get_matches();
-    let known_prefixes_from_tags: HashMap<String, String> = NumeralSystem::get_prefixes_from_tags();
-
+    // pre-defined codecs and numeral systems described in xdg files (named after the file)
+    let mut registry = Registry::builtin();
     let xdg_dirs = xdg::BaseDirectories::with_prefix("bibicode").unwrap();
-    let mut known_prefixes_from_xdgs: HashMap<String, String> = HashMap::new();
-
-    let json_files = xdg_dirs.list_data_files("");
-    let mut xdg_nums: IndexMap<&str, &str> = IndexMap::new();
-    for json_file in json_files.iter() {
-        let filename = json_file.file_stem();
-        if let Some(num) = filename {
-            xdg_nums
-                .entry(num.to_str().unwrap())
-                .or_insert(json_file.to_str().unwrap());
-            if let Some(prefix) = extract_prefix_from_path(json_file.to_str().unwrap()) {
-                known_prefixes_from_xdgs.insert(prefix, json_file.to_str().unwrap().to_string());
+    for json_file in xdg_dirs.list_data_files("").iter() {
+        let (name, path) = match (json_file.file_stem(), json_file.to_str()) {
+            (Some(name), Some(path)) => (name.to_string_lossy(), path),
+            _ => continue,
+        };
+        let registered = num_from_path(path).and_then(|num| registry.register(&name, num));
+        if let Err(err) = registered {
+            eprintln!("{} : ignored ({:?})", path, err);
+        }
+    }
+    if matches.is_present("lenient") {
+        for name in registry
+            .names()
+            .iter()
+            .map(|name| name.to_string())
+            .collect::<Vec<_>>()
+        {
+            if let Some(codec) = lenient_codec(&name) {
+                registry.remove(&name);
+                registry.register_rc(&name, codec)?;
             }
         }
     }
-    // closure to build in and out numeral system
-    let init_num = |entry: &str| -> Result<NumeralSystem, BibiError> {
+
+    // closure to get in and out codecs : a file or a registered name
+    let init_codec = |entry: &str| -> Result<Rc<dyn Codec>, BibiError> {
         if Path::new(entry).exists() {
-            return num_from_path(entry);
+            Ok(Rc::new(num_from_path(entry)?))
         } else {
-            match NumeralSystem::new_from_tag(entry) {
-                Ok(num) => return Ok(num),
-                Err(_) => {
-                    // try xdgs files
-                    if xdg_nums.contains_key(entry) {
-                        return num_from_path(xdg_nums[entry]);
-                    } else {
-                        return Err(BibiError::BadNumeralSystem);
-                    }
-                }
-            }
+            registry.get(entry).ok_or(BibiError::BadNumeralSystem)
         }
     };
 
-    let strfrom = matches.value_of("from").unwrap_or("dec");
-    let mut from: NumeralSystem;
-    if matches.value_of("from").is_none() {
-        // if entry num system not given, try to find it out
-        // from the prefix of input number
-        let input_numbers: Vec<_> = matches.values_of("INPUT").unwrap().collect();
-        let number: &str = input_numbers.first().unwrap();
-        from = init_num(strfrom)?;
-        let mut prefok = false;
-        for pref in known_prefixes_from_tags.keys() {
-            if (pref.len() > 0) && (pref.len() < number.len()) && (pref[..] == number[0..pref.len()]) {
-                prefok = true;
-                from = NumeralSystem::new_from_tag(&known_prefixes_from_tags[pref][..]).unwrap();
-            }
+    let mut pref = "";
+    if matches.is_present("outprefix") {
+        pref = matches.value_of("outprefix").unwrap();
+    }
+
+    let mut suff = "";
+    if matches.is_present("outsuffix") {
+        suff = matches.value_of("outsuffix").unwrap();
+    }
+
+    // raw binary input : the content of the file is encoded as one number
+    if let Some(path) = matches.value_of("inputbytes") {
+        let to = init_codec(matches.value_of("to").unwrap_or("dec"))?;
+        let to = to.numeral_system().ok_or(BibiError::BadNumeralSystem)?;
+        let bytes = match fs::read(path) {
+            Ok(bytes) => bytes,
+            Err(_) => return Err(BibiError::BadFile),
+        };
+        println!("{}{}{}", pref, to.encode_bytes(&bytes)?, suff);
+        return Ok(());
+    }
+
+    // without input numbers, one number is read from stdin, whitespace (line breaks) being ignored
+    let args: Option<Vec<&str>> = matches.values_of("INPUT").map(|inb| inb.collect());
+    let stdin = io::stdin();
+    let mut stdin = stdin.lock();
+    // numbers to find out the numeral system from : the beginning of stdin
+    let peeked: String;
+    let first_numbers: Vec<&str> = match args {
+        Some(ref args) => args.clone(),
+        None => {
+            let buffer = stdin.fill_buf().map_err(|_| BibiError::BadFile)?;
+            let valid = match std::str::from_utf8(buffer) {
+                Ok(text) => text.len(),
+                Err(err) => err.valid_up_to(),
+            };
+            peeked = std::str::from_utf8(&buffer[..valid])
+                .unwrap()
+                .split_whitespace()
+                .collect();
+            vec![&peeked[..]]
         }
-        if !prefok {
-            for pref in known_prefixes_from_xdgs.keys() {
-                if (pref.len() > 0) && (pref.len() < number.len()) && (pref[..] == number[0..pref.len()]) {
-                    prefok = true;
-                    from = num_from_path(&known_prefixes_from_xdgs[pref][..]).unwrap();
-                }
+    };
+
+    let from: Rc<dyn Codec> = match matches.value_of("from") {
+        Some("auto") => autodetect_codec(&registry, &first_numbers)?,
+        Some(strfrom) => init_codec(strfrom)?,
+        None => {
+            // if entry num system not given, try to find it out
+            // from the prefix of input number
+            let number: &str = first_numbers.first().unwrap();
+            match registry.autodetect(number) {
+                Some((_, detected)) => detected,
+                None => init_codec("dec")?,
             }
         }
-        if !prefok {
-            from = NumeralSystem::new_from_tag("dec").unwrap();
-        }
-    } else {
-        from = init_num(strfrom)?;
-    }
+    };
 
     let strto = matches.value_of("to").unwrap_or("dec");
-    let mut to: NumeralSystem = init_num(strto)?;
     let mut res = String::from("");
-
+    let mut to = init_codec(strto)?;
     if matches.is_present("concat") {
-        res = to.get_prefix();
-        to.set_prefix("");
+        if let Some(num) = to.numeral_system() {
+            let mut num = num.clone();
+            res = num.get_prefix();
+            num.set_prefix("");
+            to = Rc::new(num);
+        }
+    }
+
+    let mut coder = BibiCoder::new(from.clone(), to);
+
+    match matches.value_of("leadingzeros") {
+        Some("preserve") => coder.set_leading_zeros(LeadingZeros::Preserve),
+        Some("bytes") => coder.set_leading_zeros(LeadingZeros::ByteAligned),
+        _ => coder.set_leading_zeros(LeadingZeros::Drop),
+    }
+
+    if let Some(encoding) = matches.value_of("signed") {
+        let encoding = match encoding {
+            "ones" => SignedEncoding::OnesComplement,
+            "sign-magnitude" => SignedEncoding::SignMagnitude,
+            _ => SignedEncoding::TwosComplement,
+        };
+        let bits = value_t!(matches, "bits", usize).unwrap_or_else(|e| e.exit());
+        coder.set_signed(Some((encoding, bits)));
+        if let Some("output") = matches.value_of("patterns") {
+            coder.set_bit_patterns(BitPatterns::Output);
+        }
+    }
+
+    if matches.is_present("precision") {
+        let precision = value_t!(matches, "precision", usize).unwrap_or_else(|e| e.exit());
+        coder.set_precision(precision);
+    }
+
+    if let Some("half-even") = matches.value_of("rounding") {
+        coder.set_rounding(Rounding::HalfEven);
     }
 
+    if let Some(marker) = matches.values_of("repeatmarker") {
+        let marker: Vec<&str> = marker.collect();
+        coder.set_repeat_marker(marker[0], marker[1]);
+    }
+
+    // a number read from stdin is converted while it is read, if the numeral systems allow it
+    if args.is_none()
+        && coder.can_stream()
+        && !matches.is_present("regex")
+        && !matches.is_present("rational")
+        && !matches.is_present("outputbytes")
+    {
+        let stdout = io::stdout();
+        let mut stdout = stdout.lock();
+        write!(stdout, "{}{}", pref, res).map_err(|_| BibiError::BadFile)?;
+        coder.swap_stream(&mut stdin, &mut stdout)?;
+        writeln!(stdout, "{}", suff).map_err(|_| BibiError::BadFile)?;
+        return Ok(());
+    }
 
     let mut input_numbers: Vec<String> = vec![];
 
-    if let Some(inb) = matches.values_of("INPUT") {
-        //input_numbers = inb.unwrap();
+    if let Some(inb) = args {
         if matches.is_present("regex") {
             let reg = matches.value_of("regex").unwrap();
             for input_number in inb {
@@ -191,12 +452,29 @@ fn main() -> Result<(), BibiError> {
             }
         }
     } else {
-        // no input number read from stdin
-        /*let mut buffer = String::new();
-        io::stdin().read_to_string(&mut buffer)?;*/
+        let mut buffer = String::new();
+        if stdin.read_to_string(&mut buffer).is_err() {
+            return Err(BibiError::BadFile);
+        }
+        if let Some(reg) = matches.value_of("regex") {
+            input_numbers.extend(BibiCoder::extract_numbers(&buffer, reg)?);
+        } else {
+            input_numbers.push(buffer.split_whitespace().collect());
+        }
     }
 
-    let coder = BibiCoder::new(from, to);
+    // raw binary output : input numbers are decoded as bytes and written in the file
+    if let Some(path) = matches.value_of("outputbytes") {
+        let mut bytes: Vec<u8> = vec![];
+        for input_number in input_numbers.iter() {
+            let num = from.numeral_system().ok_or(BibiError::BadNumeralSystem)?;
+            bytes.extend(num.decode_bytes(input_number)?);
+        }
+        if fs::write(path, bytes).is_err() {
+            return Err(BibiError::BadFile);
+        }
+        return Ok(());
+    }
 
     let mut sep = "";
     if matches.is_present("outseparator") {
@@ -205,7 +483,11 @@ fn main() -> Result<(), BibiError> {
 
     let mut length = input_numbers.len();
     for input_number in input_numbers.iter() {
-        let output_number = coder.swap(input_number)?;
+        let output_number = if matches.is_present("rational") {
+            coder.swap_rational(input_number)?
+        } else {
+            coder.swap(input_number)?
+        };
         if matches.is_present("concat") {
             res = res + &output_number;
         } else {
@@ -214,19 +496,30 @@ fn main() -> Result<(), BibiError> {
                 res = res + &String::from(sep);
             }
         }
-        length = length - 1;
-    }
-
-    let mut pref = "";
-    if matches.is_present("outprefix") {
-        pref = matches.value_of("outprefix").unwrap();
-    }
-
-    let mut suff = "";
-    if matches.is_present("outsuffix") {
-        suff = matches.value_of("outsuffix").unwrap();
+        length -= 1;
     }
 
     println!("{}{}{}", pref, res, suff);
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cli_yaml() {
+        let yaml = load_yaml!("bibic.yaml");
+        let matches = App::from_yaml(yaml)
+            .get_matches_from_safe(vec!["bibicode", "2000", "-t", "bibi"])
+            .unwrap();
+        assert_eq!(matches.value_of("INPUT"), Some("2000"), "test 1 1");
+        assert_eq!(matches.value_of("to"), Some("bibi"), "test 1 2");
+        assert!(
+            App::from_yaml(yaml)
+                .get_matches_from_safe(vec!["bibicode", "-f", "-t", "hex", "5"])
+                .is_err(),
+            "test 1 3"
+        );
+    }
+}
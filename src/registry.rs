@@ -0,0 +1,209 @@
+// Copyright ⓒ 2019 Florent Jugla
+//
+// Licensed under MIT license ([LICENSE-MIT](LICENSE-MIT) or http://opensource.org/licenses/MIT)
+
+// Codecs known by name, and numeral systems known by prefix.
+//
+// The pre-defined ones are built once per thread, registries being then cloned from them (codecs
+// are shared).
+
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use indexmap::map::IndexMap;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use super::{BibiError, Codec, NumeralSystem, Roman, Words};
+
+thread_local! {
+    static BUILTIN: Registry = Registry::build_builtin();
+}
+
+/// Codecs registered by name. Numeral systems with a prefix are also registered by prefix, to find out the numeral system of a number (see `autodetect`).
+///
+/// ## Exemple
+///
+///        let mut registry = bibicode::Registry::builtin();
+///        let octal = bibicode::NumeralSystem::new("0q", vec!(vec!("0", "1", "2", "3", "4", "5", "6", "7"))).unwrap();
+///        registry.register("quarter", octal).unwrap();
+///        let (name, _) = registry.autodetect("0q777").unwrap();
+///        assert_eq!(name, "quarter");
+///        let coder = bibicode::BibiCoder::new(registry.get("quarter").unwrap(), registry.get("words-en").unwrap());
+///        assert_eq!(coder.swap("0q777").unwrap(), "five hundred eleven");
+#[derive(Debug, Clone, Default)]
+pub struct Registry {
+    codecs: IndexMap<String, Rc<dyn Codec>>,
+    // prefix => name
+    prefixes: HashMap<String, String>,
+}
+
+impl Registry {
+    /// Empty registry
+    pub fn new() -> Registry {
+        Registry::default()
+    }
+
+    /// Registry of the pre-defined codecs : numeral systems of `NumeralSystem::new_from_tag`, Roman numerals (`Roman::from_tag`) and words (`Words::from_tag`)
+    pub fn builtin() -> Registry {
+        BUILTIN.with(|builtin| builtin.clone())
+    }
+
+    fn build_builtin() -> Registry {
+        let mut registry = Registry::new();
+        let tags = NumeralSystem::get_tags();
+        let mixed = ["factoradic", "duration"];
+        for tag in tags.keys().chain(mixed.iter()) {
+            let num = NumeralSystem::build_from_tag(tag).unwrap();
+            registry.register(tag, num).unwrap();
+        }
+        for tag in &["roman", "roman-vinculum", "roman-apostrophus"] {
+            registry
+                .register(tag, Roman::from_tag(tag).unwrap())
+                .unwrap();
+        }
+        let words = [
+            "words-en",
+            "words-en-long",
+            "words-fr",
+            "words-fr-short",
+            "words-fr-be",
+            "words-fr-ch",
+        ];
+        for tag in &words {
+            registry
+                .register(tag, Words::from_tag(tag).unwrap())
+                .unwrap();
+        }
+        registry
+    }
+
+    /// Register a codec under a name. Gives `BibiError::DuplicateName` if the name is already registered, `BibiError::PrefixCollision` (with the name of the other numeral system) if the prefix of the numeral system is already used.
+    pub fn register<C: Codec + 'static>(&mut self, name: &str, codec: C) -> Result<(), BibiError> {
+        self.register_rc(name, Rc::new(codec))
+    }
+
+    /// Register a shared codec, see `register`
+    pub fn register_rc(&mut self, name: &str, codec: Rc<dyn Codec>) -> Result<(), BibiError> {
+        if self.codecs.contains_key(name) {
+            return Err(BibiError::DuplicateName(name.to_string()));
+        }
+        let prefix = codec
+            .numeral_system()
+            .map(|num| num.get_prefix())
+            .unwrap_or_default();
+        if !prefix.is_empty() {
+            if let Some(other) = self.prefixes.get(&prefix) {
+                return Err(BibiError::PrefixCollision(other.clone()));
+            }
+            self.prefixes.insert(prefix, name.to_string());
+        }
+        self.codecs.insert(name.to_string(), codec);
+        Ok(())
+    }
+
+    /// Remove a codec, so that its name and its prefix can be registered again
+    pub fn remove(&mut self, name: &str) -> Option<Rc<dyn Codec>> {
+        let codec = self.codecs.shift_remove(name)?;
+        self.prefixes.retain(|_, owner| owner != name);
+        Some(codec)
+    }
+
+    pub fn get(&self, name: &str) -> Option<Rc<dyn Codec>> {
+        self.codecs.get(name).cloned()
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.codecs.contains_key(name)
+    }
+
+    /// Names of the codecs, in registration order
+    pub fn names(&self) -> Vec<&str> {
+        self.codecs.keys().map(|name| &name[..]).collect()
+    }
+
+    /// Prefixes with the name of their numeral system
+    pub fn get_prefixes(&self) -> HashMap<String, String> {
+        self.prefixes.clone()
+    }
+
+    /// Find out the numeral system of a number from its prefix (the sign being written before the prefix) : the longest matching prefix wins (0xx before 0x). A number made of the prefix only is not detected.
+    pub fn autodetect(&self, number: &str) -> Option<(&str, Rc<dyn Codec>)> {
+        let mut found: Option<(&str, &Rc<dyn Codec>, usize)> = None;
+        for (prefix, name) in self.prefixes.iter() {
+            let codec = &self.codecs[name];
+            let rest = match codec.numeral_system() {
+                Some(num) => num.strip_sign(number).1,
+                None => continue,
+            };
+            if rest.len() > prefix.len()
+                && rest.starts_with(&prefix[..])
+                && found.is_none_or(|(_, _, len)| prefix.len() > len)
+            {
+                found = Some((name, codec, prefix.len()));
+            }
+        }
+        found.map(|(name, codec, _)| (name, codec.clone()))
+    }
+
+    /// Find out the codec of numbers written without prefix : every registered codec reading all the numbers is a candidate, candidates being ranked by confidence (from 0 to 1, the sum being 1).
+    ///
+    /// A number of m digits is written by chance with a probability of 1/n^m in a numeral system of n digits : small alphabets and long digits (HAHOBI is 3 digits in bibi) are preferred, and a prefix counts for nothing. Codecs which are not numeral systems read each grapheme as a digit, of an alphabet made of the graphemes of the numbers.
+    ///
+    /// ## Exemple
+    ///
+    ///        let registry = bibicode::Registry::builtin();
+    ///        let candidates = registry.rank(&["HAHOBI"]);
+    ///        assert_eq!(candidates[0].0, "bibi");
+    ///        assert!(candidates[0].1 > 0.99);
+    pub fn rank(&self, numbers: &[&str]) -> Vec<(&str, f64)> {
+        let mut candidates: Vec<(&str, f64)> = vec![];
+        for (name, codec) in self.codecs.iter() {
+            let log_chance: Option<f64> = numbers
+                .iter()
+                .map(|number| log_chance(&**codec, number))
+                .sum();
+            if let Some(log_chance) = log_chance {
+                candidates.push((name, log_chance));
+            }
+        }
+
+        let best = candidates
+            .iter()
+            .map(|(_, log_chance)| *log_chance)
+            .fold(f64::NEG_INFINITY, f64::max);
+        let total: f64 = candidates
+            .iter()
+            .map(|(_, log_chance)| (log_chance - best).exp())
+            .sum();
+        for candidate in candidates.iter_mut() {
+            candidate.1 = (candidate.1 - best).exp() / total;
+        }
+        candidates.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+        candidates
+    }
+}
+
+// log of the probability to write the number by chance with the codec, None if it can not be read
+fn log_chance(codec: &dyn Codec, number: &str) -> Option<f64> {
+    let pivot = codec.to_pivot(number).ok()?;
+    let (alphabet, len) = match codec.numeral_system() {
+        Some(num) => (alphabet_size(num), pivot.len_digits + pivot.fraction.len()),
+        None => {
+            let graphemes: Vec<&str> = number.graphemes(true).collect();
+            let mut alphabet = graphemes.clone();
+            alphabet.sort_unstable();
+            alphabet.dedup();
+            (alphabet.len().max(2), graphemes.len())
+        }
+    };
+    Some(-(len as f64) * (alphabet as f64).ln())
+}
+
+// number of digits of a numeral system, the greatest one for mixed-radix systems
+fn alphabet_size(num: &NumeralSystem) -> usize {
+    let (positions, head) = num.mixed_positions();
+    let positions = positions.iter().map(|position| position.len());
+    let head = head.into_iter().map(alphabet_size);
+    positions.chain(head).fold(num.len(), usize::max)
+}
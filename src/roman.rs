@@ -0,0 +1,208 @@
+// Copyright ⓒ 2019 Florent Jugla
+//
+// Licensed under MIT license ([LICENSE-MIT](LICENSE-MIT) or http://opensource.org/licenses/MIT)
+
+// Roman numerals are additive : they are read and written here from and to native integers.
+
+use unicode_normalization::UnicodeNormalization;
+
+use super::{BibiError, Codec, Pivot};
+
+/// Roman numerals : MMXIX for 2019. They have no sign and no fractional part, zero can not be written (`BibiError::NotRepresentable`).
+///
+/// Numbers are read strictly (only the way they are written), see `set_lenient`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Roman {
+    notation: RomanNotation,
+    // read without checking numbers are well written
+    lenient: bool,
+}
+
+impl Roman {
+    pub fn new(notation: RomanNotation) -> Roman {
+        Roman {
+            notation,
+            lenient: false,
+        }
+    }
+
+    /// Returns Roman numerals from their tag : roman, roman-vinculum or roman-apostrophus
+    pub fn from_tag(tag: &str) -> Result<Roman, BibiError> {
+        match tag {
+            "roman" => Ok(Roman::new(RomanNotation::Standard)),
+            "roman-vinculum" => Ok(Roman::new(RomanNotation::Vinculum)),
+            "roman-apostrophus" => Ok(Roman::new(RomanNotation::Apostrophus)),
+            _ => Err(BibiError::BadTagNumeralSystem),
+        }
+    }
+
+    pub fn get_notation(&self) -> RomanNotation {
+        self.notation
+    }
+
+    pub fn is_lenient(&self) -> bool {
+        self.lenient
+    }
+
+    /// Read numbers leniently : lower case letters and any additive or subtractive writing are accepted (IIII, IIX for 8, mcmxcix). Strict reading only accepts numbers written the way they are written.
+    pub fn set_lenient(&mut self, lenient: bool) {
+        self.lenient = lenient;
+    }
+}
+
+impl Codec for Roman {
+    fn to_pivot(&self, entry: &str) -> Result<Pivot, BibiError> {
+        let entry: String = entry.nfc().collect();
+        let number = parse(&entry, self.notation, !self.lenient)
+            .ok_or(BibiError::EntryMismatchWithNumeralSystem)?;
+        Ok(Pivot::from_u128(number as u128, false))
+    }
+
+    fn from_pivot(&self, pivot: &Pivot) -> Result<String, BibiError> {
+        if !pivot.is_integer() {
+            return Err(BibiError::FractionNotSupported);
+        }
+        if pivot.is_negative() && !pivot.is_zero() {
+            return Err(BibiError::NegativeNotSupported);
+        }
+        let number = pivot.to_u128().map_err(|_| BibiError::NotRepresentable)?;
+        if number > self.notation.max_value() as u128 {
+            return Err(BibiError::NotRepresentable);
+        }
+        format(number as u64, self.notation).ok_or(BibiError::NotRepresentable)
+    }
+}
+
+/// Notation of Roman numerals above 3999.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RomanNotation {
+    /// I, V, X, L, C, D, M : up to 3999 (MMMCMXCIX)
+    #[default]
+    Standard,
+    /// A bar multiplies by 1000 (V̄ for 5000, M̄ for 1000000) : up to 3999999
+    Vinculum,
+    /// Apostrophus and reversed C (CIↃ for 1000, IↃↃ for 5000, CCIↃↃ for 10000...) : up to 399999
+    Apostrophus,
+}
+
+impl RomanNotation {
+    // symbols with their value, alternatively for 1 and 5 times a power of ten
+    fn symbols(self) -> Vec<(u64, &'static str)> {
+        let mut symbols = vec![
+            (1, "I"),
+            (5, "V"),
+            (10, "X"),
+            (50, "L"),
+            (100, "C"),
+            (500, "D"),
+        ];
+        match self {
+            RomanNotation::Standard => symbols.push((1000, "M")),
+            RomanNotation::Vinculum => symbols.extend(vec![
+                (1000, "M"),
+                (5000, "V\u{304}"),
+                (10000, "X\u{304}"),
+                (50000, "L\u{304}"),
+                (100_000, "C\u{304}"),
+                (500_000, "D\u{304}"),
+                (1_000_000, "M\u{304}"),
+            ]),
+            RomanNotation::Apostrophus => symbols.extend(vec![
+                (1000, "CIↃ"),
+                (5000, "IↃↃ"),
+                (10000, "CCIↃↃ"),
+                (50000, "IↃↃↃ"),
+                (100_000, "CCCIↃↃↃ"),
+            ]),
+        }
+        symbols
+    }
+
+    /// Greatest number which can be written, the greatest symbol being repeated 3 times
+    pub fn max_value(self) -> u64 {
+        let top = self.symbols().last().unwrap().0;
+        4 * top - 1
+    }
+}
+
+// write a number from 1 to max_value
+fn format(number: u64, notation: RomanNotation) -> Option<String> {
+    if number == 0 || number > notation.max_value() {
+        return None;
+    }
+    let symbols = notation.symbols();
+    let mut ret = String::new();
+    // from the greatest power of ten, written with its symbols for one, five and ten
+    for idx in (0..symbols.len()).step_by(2).rev() {
+        let (unit, one) = symbols[idx];
+        let digit = if idx + 1 == symbols.len() {
+            number / unit
+        } else {
+            number / unit % 10
+        };
+        let pattern = match digit {
+            0 => vec![],
+            1..=3 => vec![one; digit as usize],
+            4 => vec![one, symbols[idx + 1].1],
+            5..=8 => {
+                let mut pattern = vec![symbols[idx + 1].1];
+                pattern.extend(vec![one; digit as usize - 5]);
+                pattern
+            }
+            _ => vec![one, symbols[idx + 2].1],
+        };
+        ret += &pattern.concat();
+    }
+    Some(ret)
+}
+
+// read a number. Lenient reading accepts lower case letters and any additive or subtractive writing
+// (IIII, IIX for 8, MDCCCCX), strict reading only accepts the numbers as they are written by format.
+fn parse(entry: &str, notation: RomanNotation, strict: bool) -> Option<u64> {
+    let lenient: String;
+    let entry = if strict {
+        entry
+    } else {
+        lenient = entry
+            .chars()
+            .map(|c| match c {
+                'Ɔ' | 'ↄ' => 'Ↄ',
+                _ => c.to_ascii_uppercase(),
+            })
+            .collect();
+        &lenient[..]
+    };
+
+    // longest symbols first
+    let mut symbols = notation.symbols();
+    symbols.sort_by_key(|(_, symbol)| std::cmp::Reverse(symbol.len()));
+    let mut values: Vec<u64> = vec![];
+    let mut rest = entry;
+    while !rest.is_empty() {
+        let (value, symbol) = symbols
+            .iter()
+            .find(|(_, symbol)| rest.starts_with(symbol))?;
+        values.push(*value);
+        rest = &rest[symbol.len()..];
+    }
+
+    // a symbol followed somewhere by a greater one is subtracted (IIX for 8)
+    let mut total: i64 = 0;
+    let mut greatest = 0;
+    for value in values.into_iter().rev() {
+        if value < greatest {
+            total -= value as i64;
+        } else {
+            total += value as i64;
+            greatest = value;
+        }
+    }
+    if total <= 0 {
+        return None;
+    }
+    let number = total as u64;
+    if strict && format(number, notation).as_deref() != Some(entry) {
+        return None;
+    }
+    Some(number)
+}
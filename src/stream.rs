@@ -0,0 +1,536 @@
+// Copyright ⓒ 2019 Florent Jugla
+//
+// Licensed under MIT license ([LICENSE-MIT](LICENSE-MIT) or http://opensource.org/licenses/MIT)
+
+// Numbers read from a stream and written to another one digit by digit, without holding them in
+// memory.
+//
+// Input digits give output digits as soon as they are read when each of them gives whole output
+// digits (same radix, hex to bin...). Otherwise the first output digit depends on the length of the
+// integer part (hex to base32) : the bits of the input digits are packed until its end. Fractional
+// digits are always regrouped as they are read, from the radix point.
+
+use std::collections::VecDeque;
+use std::io::{BufWriter, ErrorKind, Read, Write};
+
+use unicode_normalization::UnicodeNormalization;
+use unicode_segmentation::UnicodeSegmentation;
+
+use super::{BibiError, LeadingZeros, NumeralSystem};
+
+// bytes read at once from the input
+const BLOCK_SIZE: usize = 1 << 16;
+
+// swap a number read from input in num_in to output in num_out, the numeral systems having the same
+// radix or radices which are powers of two. The fractional part is truncated to precision digits.
+pub(crate) fn swap<R: Read, W: Write>(
+    input: R,
+    output: W,
+    num_in: &NumeralSystem,
+    num_out: &NumeralSystem,
+    leading_zeros: LeadingZeros,
+    precision: usize,
+) -> Result<(), BibiError> {
+    let mut reader = DigitReader::new(input, num_in);
+    let negative = reader.read_sign()?;
+    let mut writer = DigitWriter::new(output, num_out, negative);
+    writer.preserve = leading_zeros == LeadingZeros::Preserve;
+
+    let bits = num_in.bit_group().zip(num_out.bit_group());
+    let same = num_in.radix() == num_out.radix();
+    let (bits_in, bits_out) = bits.unwrap_or((0, 0));
+    let mut integer = match bits {
+        _ if same => Integer::Same,
+        Some(_) if bits_in % bits_out == 0 => Integer::Aligned(BitGroups::new(bits_out, 0)),
+        _ => Integer::Packed(BitGroups::new(32, 0), vec![]),
+    };
+    let mut fraction = BitGroups::new(bits_out, 0);
+
+    let mut len_digits = 0;
+    let mut significant = 0;
+    let mut radix_point = false;
+    let mut fraction_len = 0;
+    let mut fraction_zero = true;
+    while let Some(token) = reader.next_token()? {
+        let digit = match token {
+            Token::Digit(digit) => digit,
+            Token::RadixPoint if !radix_point => {
+                radix_point = true;
+                integer.finish(&mut writer, significant * bits_in)?;
+                continue;
+            }
+            Token::RadixPoint => return Err(BibiError::EntryMismatchWithNumeralSystem),
+        };
+
+        if radix_point {
+            fraction_len += 1;
+            // as swap does, before writing anything
+            if fraction_zero && digit > 0 {
+                fraction_zero = false;
+                if num_out.radix_point.is_empty() {
+                    return Err(BibiError::FractionNotSupported);
+                }
+                if negative && !num_out.writes_negative() {
+                    return Err(BibiError::NegativeNotSupported);
+                }
+            }
+            if same {
+                writer.write_fraction(digit, precision)?;
+            } else {
+                fraction.push(digit, bits_in);
+                while let Some(group) = fraction.pop() {
+                    writer.write_fraction(group, precision)?;
+                }
+            }
+            continue;
+        }
+
+        len_digits += 1;
+        if significant == 0 && digit == 0 {
+            writer.zero_digits += 1;
+            continue;
+        }
+        significant += 1;
+        integer.push(digit, bits_in, &mut writer)?;
+    }
+
+    if !radix_point {
+        if len_digits == 0 {
+            return Err(BibiError::EntryMismatchWithNumeralSystem);
+        }
+        integer.finish(&mut writer, significant * bits_in)?;
+    } else if len_digits == 0 && fraction_len == 0 {
+        // a radix point alone
+        return Err(BibiError::EntryMismatchWithNumeralSystem);
+    }
+    // the last fractional digit is padded with zeros
+    let (rest, len) = fraction.rest();
+    if len > 0 {
+        writer.write_fraction(rest << (bits_out - len), precision)?;
+    }
+    writer.finish()
+}
+
+// what is read from the input
+enum Token {
+    Digit(u32),
+    RadixPoint,
+}
+
+// how input digits of the integer part give output digits
+enum Integer {
+    // same radix : each input digit is an output digit
+    Same,
+    // each input digit gives whole output digits
+    Aligned(BitGroups),
+    // input digits packed in 32 bits words (the last one being filled), regrouped at the end of the
+    // integer part
+    Packed(BitGroups, Vec<u32>),
+}
+
+impl Integer {
+    // a significant digit
+    fn push<W: Write>(
+        &mut self,
+        digit: u32,
+        bits_in: usize,
+        writer: &mut DigitWriter<W>,
+    ) -> Result<(), BibiError> {
+        match *self {
+            Integer::Same => writer.write(digit)?,
+            Integer::Aligned(ref mut groups) => {
+                groups.push(digit, bits_in);
+                while let Some(group) = groups.pop() {
+                    writer.write(group)?;
+                }
+            }
+            Integer::Packed(ref mut word, ref mut packed) => {
+                word.push(digit, bits_in);
+                packed.extend(word.pop());
+            }
+        }
+        Ok(())
+    }
+
+    // the number of significant bits is known : the first output digit is padded with zeros
+    fn finish<W: Write>(
+        &mut self,
+        writer: &mut DigitWriter<W>,
+        len_bits: usize,
+    ) -> Result<(), BibiError> {
+        if let Integer::Packed(ref word, ref mut packed) = *self {
+            let bits_out = writer.num.bit_group().unwrap();
+            let pad = (bits_out - len_bits % bits_out) % bits_out;
+            let mut groups = BitGroups::new(bits_out, pad);
+            let words = packed.drain(..).map(|word| (word, 32));
+            for (bits, len) in words.chain(Some(word.rest())) {
+                groups.push(bits, len);
+                while let Some(group) = groups.pop() {
+                    writer.write(group)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+// bits given in groups of any size and taken in groups of bits bits, most significant first
+struct BitGroups {
+    pending: u64,
+    len: usize,
+    bits: usize,
+}
+
+impl BitGroups {
+    // the first group begins with pad zero bits
+    fn new(bits: usize, pad: usize) -> BitGroups {
+        BitGroups {
+            pending: 0,
+            len: pad,
+            bits,
+        }
+    }
+
+    // at most 32 bits, the groups being taken before
+    fn push(&mut self, value: u32, len: usize) {
+        self.pending = (self.pending << len) | value as u64;
+        self.len += len;
+    }
+
+    fn pop(&mut self) -> Option<u32> {
+        if self.len < self.bits {
+            return None;
+        }
+        self.len -= self.bits;
+        let group = (self.pending >> self.len) as u32;
+        self.pending &= (1 << self.len) - 1;
+        Some(group)
+    }
+
+    // bits left, and their number
+    fn rest(&self) -> (u32, usize) {
+        (self.pending as u32, self.len)
+    }
+}
+
+// digits of a number read from a stream, whitespace being ignored
+struct DigitReader<'a, R: Read> {
+    input: R,
+    num: &'a NumeralSystem,
+    block: Vec<u8>,
+    // bytes of an incomplete character at the end of the last block
+    partial: Vec<u8>,
+    // text read, without whitespace : its last grapheme may go on in the next block
+    text: String,
+    // normalized text not yet split : beginning of a digit or of the radix point
+    pending: String,
+    tokens: VecDeque<Token>,
+    // a digit must follow the last separator
+    separated: bool,
+    eof: bool,
+}
+
+impl<'a, R: Read> DigitReader<'a, R> {
+    fn new(input: R, num: &'a NumeralSystem) -> DigitReader<'a, R> {
+        DigitReader {
+            input,
+            num,
+            block: vec![0; BLOCK_SIZE],
+            partial: vec![],
+            text: String::new(),
+            pending: String::new(),
+            tokens: VecDeque::new(),
+            separated: false,
+            eof: false,
+        }
+    }
+
+    // read the sign and the prefix, if any. Returns true for a minus sign.
+    fn read_sign(&mut self) -> Result<bool, BibiError> {
+        let num = self.num;
+        let signs = num.minus.iter().chain(num.plus.iter());
+        let header = signs.map(|sign| sign.len()).max().unwrap_or(0) + num.prefix.len();
+        while !self.eof && self.complete_len() <= header {
+            self.read_block()?;
+        }
+        let text = self.take_complete();
+        let (sign, rest) = num.strip_sign(&text);
+        let rest = match rest.strip_prefix(&num.prefix[..]) {
+            Some(digits) if !num.prefix.is_empty() && (!digits.is_empty() || !self.eof) => digits,
+            _ => rest,
+        };
+        self.pending = rest.to_string();
+        self.split_digits()?;
+        Ok(sign == Some(true))
+    }
+
+    fn next_token(&mut self) -> Result<Option<Token>, BibiError> {
+        while self.tokens.is_empty() {
+            if self.eof {
+                return Ok(None);
+            }
+            self.read_block()?;
+            let text = self.take_complete();
+            self.pending += &text;
+            self.split_digits()?;
+        }
+        Ok(self.tokens.pop_front())
+    }
+
+    fn read_block(&mut self) -> Result<(), BibiError> {
+        let len = loop {
+            match self.input.read(&mut self.block) {
+                Ok(len) => break len,
+                Err(ref err) if err.kind() == ErrorKind::Interrupted => continue,
+                Err(_) => return Err(BibiError::BadFile),
+            }
+        };
+        if len == 0 {
+            self.eof = true;
+            if !self.partial.is_empty() {
+                return Err(BibiError::EntryMismatchWithNumeralSystem);
+            }
+            return Ok(());
+        }
+        self.partial.extend_from_slice(&self.block[..len]);
+        let valid = match std::str::from_utf8(&self.partial) {
+            Ok(text) => text.len(),
+            Err(err) if err.error_len().is_none() => err.valid_up_to(),
+            Err(_) => return Err(BibiError::EntryMismatchWithNumeralSystem),
+        };
+        let text = std::str::from_utf8(&self.partial[..valid]).unwrap();
+        self.text
+            .extend(text.chars().filter(|c| !c.is_whitespace()));
+        self.partial.drain(..valid);
+        Ok(())
+    }
+
+    // length of the text read which can not go on, ie up to its last grapheme (all of it at the end
+    // of the stream)
+    fn complete_len(&self) -> usize {
+        if self.eof {
+            return self.text.len();
+        }
+        self.text
+            .grapheme_indices(true)
+            .next_back()
+            .map_or(0, |(idx, _)| idx)
+    }
+
+    fn take_complete(&mut self) -> String {
+        let len = self.complete_len();
+        let text = &self.text[..len];
+        // ascii text is normalized
+        let ret = if text.is_ascii() {
+            text.to_string()
+        } else {
+            text.nfc().collect()
+        };
+        self.text.drain(..len);
+        ret
+    }
+
+    // split the pending text into digits and radix point, keeping the beginning of the last digit
+    // until the end of the stream
+    fn split_digits(&mut self) -> Result<(), BibiError> {
+        let num = self.num;
+        let radix_point = &num.radix_point[..];
+        let end = self.eof && self.text.is_empty();
+        let mut start = 0;
+        if num.separator.is_empty() {
+            // digits are prefix-free : a digit is complete as soon as it is found
+            let mut node = 0;
+            let mut pos = 0;
+            while pos < self.pending.len() {
+                let rest = &self.pending[pos..];
+                if node == 0 && !radix_point.is_empty() {
+                    if rest.starts_with(radix_point) {
+                        self.tokens.push_back(Token::RadixPoint);
+                        pos += radix_point.len();
+                        start = pos;
+                        continue;
+                    }
+                    if !end && radix_point.starts_with(rest) {
+                        break;
+                    }
+                }
+                // an ascii character followed by another one is a grapheme
+                let bytes = rest.as_bytes();
+                let grapheme = if bytes[..bytes.len().min(2)].is_ascii() {
+                    &rest[..1]
+                } else {
+                    rest.graphemes(true).next().unwrap()
+                };
+                node = match num.trie.nodes[node].children.get(grapheme) {
+                    Some(next) => *next,
+                    None => return Err(BibiError::EntryMismatchWithNumeralSystem),
+                };
+                pos += grapheme.len();
+                if let Some(value) = num.trie.nodes[node].value {
+                    self.tokens.push_back(Token::Digit(value));
+                    node = 0;
+                    start = pos;
+                }
+            }
+        } else {
+            // digits end with the separator or the radix point
+            loop {
+                let rest = &self.pending[start..];
+                let separator = rest.find(&num.separator[..]);
+                let point = match radix_point {
+                    "" => None,
+                    _ => rest.find(radix_point),
+                };
+                let (len, is_point) = match (separator, point) {
+                    (_, Some(point)) if separator.is_none_or(|separator| point < separator) => {
+                        (point, true)
+                    }
+                    (Some(separator), _) => (separator, false),
+                    _ if end && !rest.is_empty() => (rest.len(), false),
+                    _ => break,
+                };
+                // the integer or fractional part may be empty
+                if len > 0 || !is_point || self.separated {
+                    let value = num
+                        .digits
+                        .get(&rest[..len])
+                        .ok_or(BibiError::EntryMismatchWithNumeralSystem)?;
+                    self.tokens.push_back(Token::Digit(*value));
+                }
+                start += len;
+                self.separated = !is_point && start < self.pending.len();
+                if is_point {
+                    self.tokens.push_back(Token::RadixPoint);
+                    start += radix_point.len();
+                } else if start < self.pending.len() {
+                    start += num.separator.len();
+                }
+            }
+        }
+        self.pending.drain(..start);
+        if end && (!self.pending.is_empty() || self.separated) {
+            return Err(BibiError::EntryMismatchWithNumeralSystem);
+        }
+        Ok(())
+    }
+}
+
+// digits written to a stream : leading zeros of the integer part and trailing zeros of the
+// fractional part are dropped
+struct DigitWriter<'a, W: Write> {
+    output: BufWriter<W>,
+    num: &'a NumeralSystem,
+    negative: bool,
+    // leading zeros of the input, written with preserve
+    zero_digits: usize,
+    preserve: bool,
+    // the sign and the prefix are written
+    started: bool,
+    separate: bool,
+    // fractional digits written, and zeros not written yet
+    fraction_digits: usize,
+    fraction_zeros: usize,
+}
+
+impl<'a, W: Write> DigitWriter<'a, W> {
+    fn new(output: W, num: &'a NumeralSystem, negative: bool) -> DigitWriter<'a, W> {
+        DigitWriter {
+            output: BufWriter::new(output),
+            num,
+            negative,
+            zero_digits: 0,
+            preserve: false,
+            started: false,
+            separate: false,
+            fraction_digits: 0,
+            fraction_zeros: 0,
+        }
+    }
+
+    // digit of the integer part
+    fn write(&mut self, digit: u32) -> Result<(), BibiError> {
+        if !self.started {
+            if digit == 0 {
+                return Ok(());
+            }
+            let negative = self.negative;
+            self.start(negative)?;
+            if self.preserve {
+                for _ in 0..self.zero_digits {
+                    self.put(0)?;
+                }
+            }
+        }
+        self.put(digit)
+    }
+
+    // digit of the fractional part, those after precision digits being dropped
+    fn write_fraction(&mut self, digit: u32, precision: usize) -> Result<(), BibiError> {
+        if self.fraction_digits + self.fraction_zeros >= precision {
+            return Ok(());
+        }
+        if digit == 0 {
+            self.fraction_zeros += 1;
+            return Ok(());
+        }
+        if self.fraction_digits == 0 {
+            if !self.started {
+                let negative = self.negative;
+                self.write_zero(negative)?;
+            }
+            let num = self.num;
+            self.put_str(&num.radix_point)?;
+            self.separate = false;
+        }
+        for _ in 0..self.fraction_zeros {
+            self.put(0)?;
+        }
+        self.fraction_digits += self.fraction_zeros + 1;
+        self.fraction_zeros = 0;
+        self.put(digit)
+    }
+
+    fn finish(mut self) -> Result<(), BibiError> {
+        if !self.started {
+            self.write_zero(false)?;
+        }
+        self.output.flush().map_err(|_| BibiError::BadFile)
+    }
+
+    // null integer part : as many zeros as read with preserve
+    fn write_zero(&mut self, negative: bool) -> Result<(), BibiError> {
+        self.start(negative)?;
+        let zeros = if self.preserve { self.zero_digits } else { 1 };
+        for _ in 0..zeros.max(1) {
+            self.put(0)?;
+        }
+        Ok(())
+    }
+
+    fn start(&mut self, negative: bool) -> Result<(), BibiError> {
+        let num = self.num;
+        if negative && !num.writes_negative() {
+            return Err(BibiError::NegativeNotSupported);
+        }
+        self.started = true;
+        if negative {
+            self.put_str(num.minus_sign())?;
+        }
+        self.put_str(&num.prefix)
+    }
+
+    fn put(&mut self, digit: u32) -> Result<(), BibiError> {
+        let num = self.num;
+        if self.separate {
+            self.put_str(&num.separator)?;
+        }
+        self.separate = true;
+        self.put_str(&num.revdigits[&digit])
+    }
+
+    fn put_str(&mut self, text: &str) -> Result<(), BibiError> {
+        self.output
+            .write_all(text.as_bytes())
+            .map_err(|_| BibiError::BadFile)
+    }
+}
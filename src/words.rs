@@ -0,0 +1,501 @@
+// Copyright ⓒ 2019 Florent Jugla
+//
+// Licensed under MIT license ([LICENSE-MIT](LICENSE-MIT) or http://opensource.org/licenses/MIT)
+
+// Numbers written as words, read and written from and to limbs (least significant first).
+//
+// Names of large numbers are built from latin roots (Conway-Wechsler system) : million, billion,
+// ..., decillion, ..., centillion, ..., millinillion, so that any number can be written.
+
+use std::collections::HashMap;
+
+use unicode_normalization::UnicodeNormalization;
+
+use super::{limbs_add, limbs_divmod_small, limbs_mul, limbs_mul_small, limbs_trim};
+use super::{BibiError, Codec, Pivot};
+
+/// Numbers written as words, in any language and scale : 2000 is written `two thousand` or `deux mille`, 1000000000 `one billion` in short scale or `un milliard` in long scale. Large numbers are named as in the Conway-Wechsler system (decillion, centillion, millinillion...), so that any number can be written. Words have no sign and no fractional part.
+///
+/// Numbers are read strictly (only the way they are written), see `set_lenient`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Words {
+    language: Language,
+    scale: Scale,
+    // read without checking numbers are well written
+    lenient: bool,
+}
+
+impl Words {
+    pub fn new(language: Language, scale: Scale) -> Words {
+        Words {
+            language,
+            scale,
+            lenient: false,
+        }
+    }
+
+    /// Returns words from their tag :
+    /// - words-en for english in short scale, words-en-long in long scale
+    /// - words-fr for french in long scale, words-fr-short in short scale
+    /// - words-fr-be and words-fr-ch for belgian and swiss french (septante, nonante)
+    pub fn from_tag(tag: &str) -> Result<Words, BibiError> {
+        match tag {
+            "words-en" => Ok(Words::new(Language::English, Scale::Short)),
+            "words-en-long" => Ok(Words::new(Language::English, Scale::Long)),
+            "words-fr" => Ok(Words::new(Language::French, Scale::Long)),
+            "words-fr-short" => Ok(Words::new(Language::French, Scale::Short)),
+            "words-fr-be" => Ok(Words::new(Language::FrenchBelgian, Scale::Long)),
+            "words-fr-ch" => Ok(Words::new(Language::FrenchSwiss, Scale::Long)),
+            _ => Err(BibiError::BadTagNumeralSystem),
+        }
+    }
+
+    pub fn get_language(&self) -> Language {
+        self.language
+    }
+
+    pub fn get_scale(&self) -> Scale {
+        self.scale
+    }
+
+    pub fn is_lenient(&self) -> bool {
+        self.lenient
+    }
+
+    /// Read numbers leniently : any case, "and" and any spelling made of the words of the language are accepted (One Hundred and Two, deux cent). Strict reading only accepts numbers written the way they are written.
+    pub fn set_lenient(&mut self, lenient: bool) {
+        self.lenient = lenient;
+    }
+}
+
+impl Codec for Words {
+    fn to_pivot(&self, entry: &str) -> Result<Pivot, BibiError> {
+        let entry: String = entry.nfc().collect();
+        let limbs = parse(&entry, self.language, self.scale, !self.lenient)
+            .ok_or(BibiError::EntryMismatchWithNumeralSystem)?;
+        Ok(Pivot::from_limbs(&limbs, false))
+    }
+
+    fn from_pivot(&self, pivot: &Pivot) -> Result<String, BibiError> {
+        if !pivot.is_integer() {
+            return Err(BibiError::FractionNotSupported);
+        }
+        if pivot.is_negative() && !pivot.is_zero() {
+            return Err(BibiError::NegativeNotSupported);
+        }
+        Ok(format(&pivot.limbs, self.language, self.scale))
+    }
+}
+
+/// Language in which numbers are written as words.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Language {
+    /// two thousand, twenty-one
+    #[default]
+    English,
+    /// deux mille, soixante-dix, quatre-vingt-dix
+    French,
+    /// septante, quatre-vingts, nonante as in Belgium
+    FrenchBelgian,
+    /// septante, huitante, nonante as in Switzerland
+    FrenchSwiss,
+}
+
+/// Scale used to name large numbers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Scale {
+    /// Each name is 1000 times the previous one : billion is 10^9, trillion 10^12
+    #[default]
+    Short,
+    /// Each name is 1000000 times the previous one : billion is 10^12 (10^9 being a thousand million in
+    /// English and a milliard in French)
+    Long,
+}
+
+// greatest power of ten read, its computation being quadratic
+const MAX_EXPONENT: usize = 1 << 16;
+
+const ONES_EN: [&str; 20] = [
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+
+const TENS_EN: [&str; 10] = [
+    "", "ten", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+
+const ONES_FR: [&str; 17] = [
+    "zéro", "un", "deux", "trois", "quatre", "cinq", "six", "sept", "huit", "neuf", "dix", "onze",
+    "douze", "treize", "quatorze", "quinze", "seize",
+];
+
+const TENS_FR: [&str; 10] = [
+    "",
+    "dix",
+    "vingt",
+    "trente",
+    "quarante",
+    "cinquante",
+    "soixante",
+    "septante",
+    "huitante",
+    "nonante",
+];
+
+// latin roots of the names of large numbers
+const SMALL_ROOTS: [&str; 10] = [
+    "n", "m", "b", "tr", "quadr", "quint", "sext", "sept", "oct", "non",
+];
+
+const UNIT_ROOTS: [&str; 10] = [
+    "", "un", "duo", "tre", "quattuor", "quin", "se", "septe", "octo", "nove",
+];
+
+// roots with the marks changing the unit written before them
+const TEN_ROOTS: [(&str, &str); 10] = [
+    ("", ""),
+    ("deci", "n"),
+    ("viginti", "ms"),
+    ("triginta", "ns"),
+    ("quadraginta", "ns"),
+    ("quinquaginta", "ns"),
+    ("sexaginta", "n"),
+    ("septuaginta", "n"),
+    ("octoginta", "mx"),
+    ("nonaginta", ""),
+];
+
+const HUNDRED_ROOTS: [(&str, &str); 10] = [
+    ("", ""),
+    ("centi", "nx"),
+    ("ducenti", "n"),
+    ("trecenti", "ns"),
+    ("quadringenti", "ns"),
+    ("quingenti", "ns"),
+    ("sescenti", "n"),
+    ("septingenti", "n"),
+    ("octingenti", "mx"),
+    ("nongenti", ""),
+];
+
+impl Language {
+    fn is_french(self) -> bool {
+        self != Language::English
+    }
+
+    fn zero(self) -> &'static str {
+        if self.is_french() {
+            ONES_FR[0]
+        } else {
+            ONES_EN[0]
+        }
+    }
+}
+
+// latin root of a number from 0 to 999
+fn root(number: usize, french: bool) -> String {
+    if number < 10 {
+        return String::from(SMALL_ROOTS[number]);
+    }
+    let (units, tens, hundreds) = (number % 10, number / 10 % 10, number / 100);
+    let marks = if tens > 0 {
+        TEN_ROOTS[tens].1
+    } else {
+        HUNDRED_ROOTS[hundreds].1
+    };
+    let unit = match units {
+        3 if marks.contains('s') || marks.contains('x') => "tres",
+        6 if marks.contains('x') => "sex",
+        6 if marks.contains('s') => "ses",
+        7 if marks.contains('m') => "septem",
+        7 if marks.contains('n') => "septen",
+        9 if marks.contains('m') => "novem",
+        9 if marks.contains('n') => "noven",
+        _ => UNIT_ROOTS[units],
+    };
+    let ten = if french && tens == 1 {
+        "déci"
+    } else {
+        TEN_ROOTS[tens].0
+    };
+    let mut ret = String::from(unit) + ten + HUNDRED_ROOTS[hundreds].0;
+    if ret.ends_with('a') || ret.ends_with('i') {
+        ret.pop();
+    }
+    ret
+}
+
+// name of the rank-th large number (1 for million, 2 for billion...), ending with "on" or "ard"
+fn name(mut rank: usize, suffix: &str, french: bool) -> String {
+    let mut roots = vec![];
+    while rank > 0 {
+        roots.push(root(rank % 1000, french));
+        rank /= 1000;
+    }
+    roots.reverse();
+    roots.join("illi") + "illi" + suffix
+}
+
+// rank of a large number and true if its name ends with "ard"
+fn read_name(word: &str, roots: &HashMap<String, usize>) -> Option<(usize, bool)> {
+    let word = word.strip_suffix('s').unwrap_or(word);
+    let (body, ard) = if let Some(body) = word.strip_suffix("illion") {
+        (body, false)
+    } else {
+        (word.strip_suffix("illiard")?, true)
+    };
+    let mut rank: usize = 0;
+    for part in body.split("illi") {
+        rank = rank.checked_mul(1000)? + roots.get(part)?;
+    }
+    if rank == 0 {
+        return None;
+    }
+    Some((rank, ard))
+}
+
+// power of ten given by the name of a large number
+fn exponent(language: Language, scale: Scale, rank: usize, ard: bool) -> Option<usize> {
+    match scale {
+        Scale::Short if !ard => rank.checked_mul(3)?.checked_add(3),
+        Scale::Long if !ard => rank.checked_mul(6),
+        Scale::Long if language.is_french() => rank.checked_mul(6)?.checked_add(3),
+        _ => None,
+    }
+}
+
+fn power_of_ten(exponent: usize) -> Vec<u32> {
+    let mut ret = vec![1];
+    let mut square = vec![10];
+    let mut exponent = exponent;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            ret = limbs_mul(&ret, &square);
+        }
+        exponent >>= 1;
+        if exponent > 0 {
+            square = limbs_mul(&square, &square);
+        }
+    }
+    ret
+}
+
+fn tens_en(number: usize) -> String {
+    if number < 20 {
+        String::from(ONES_EN[number])
+    } else if number.is_multiple_of(10) {
+        String::from(TENS_EN[number / 10])
+    } else {
+        String::from(TENS_EN[number / 10]) + "-" + ONES_EN[number % 10]
+    }
+}
+
+// number from 1 to 999
+fn chunk_en(number: usize) -> String {
+    let mut parts = vec![];
+    if number >= 100 {
+        parts.push(String::from(ONES_EN[number / 100]) + " hundred");
+    }
+    if !number.is_multiple_of(100) {
+        parts.push(tens_en(number % 100));
+    }
+    parts.join(" ")
+}
+
+// number from 1 to 99, plural being true if quatre-vingts can be written with its s
+fn tens_fr(number: usize, language: Language, plural: bool) -> String {
+    if number < 17 {
+        return String::from(ONES_FR[number]);
+    }
+    if number < 20 {
+        return String::from("dix-") + ONES_FR[number - 10];
+    }
+    let (tens, units) = (number / 10, number % 10);
+    let (base, rest) = match (tens, language) {
+        (7, Language::French) => (TENS_FR[6], 10 + units),
+        (8, Language::FrenchSwiss) => (TENS_FR[8], units),
+        (8, _) => ("quatre-vingt", units),
+        (9, Language::French) => ("quatre-vingt", 10 + units),
+        _ => (TENS_FR[tens], units),
+    };
+    let four_twenty = base == "quatre-vingt";
+    if rest == 0 {
+        String::from(base) + if four_twenty && plural { "s" } else { "" }
+    } else if (rest == 1 || rest == 11) && !four_twenty {
+        String::from(base) + " et " + &tens_fr(rest, language, plural)
+    } else {
+        String::from(base) + "-" + &tens_fr(rest, language, plural)
+    }
+}
+
+// number from 1 to 999, plural being true if cents and quatre-vingts can be written with their s
+// (at the end of the number or before million, milliard...)
+fn chunk_fr(number: usize, language: Language, plural: bool) -> String {
+    let mut parts = vec![];
+    let (hundreds, rest) = (number / 100, number % 100);
+    if hundreds == 1 {
+        parts.push(String::from("cent"));
+    } else if hundreds > 1 {
+        let s = if rest == 0 && plural { "s" } else { "" };
+        parts.push(String::from(ONES_FR[hundreds]) + " cent" + s);
+    }
+    if rest > 0 {
+        parts.push(tens_fr(rest, language, plural));
+    }
+    parts.join(" ")
+}
+
+// write a number given by its limbs
+fn format(limbs: &[u32], language: Language, scale: Scale) -> String {
+    let mut number = limbs.to_vec();
+    limbs_trim(&mut number);
+    if number.is_empty() {
+        return String::from(language.zero());
+    }
+    // groups of three digits, least significant first
+    let mut groups: Vec<usize> = vec![];
+    while !number.is_empty() {
+        let rem = limbs_divmod_small(&mut number, 1_000_000_000) as usize;
+        groups.extend(vec![rem % 1000, rem / 1000 % 1000, rem / 1_000_000]);
+    }
+    while groups.last() == Some(&0) {
+        groups.pop();
+    }
+
+    let french = language.is_french();
+    let mut parts: Vec<String> = vec![];
+    if !french && scale == Scale::Long {
+        // a thousand million, groups of six digits being named
+        for idx in (0..groups.len().div_ceil(2)).rev() {
+            let high = *groups.get(2 * idx + 1).unwrap_or(&0);
+            let low = groups[2 * idx];
+            if high == 0 && low == 0 {
+                continue;
+            }
+            let mut words = vec![];
+            if high > 0 {
+                words.push(chunk_en(high) + " thousand");
+            }
+            if low > 0 {
+                words.push(chunk_en(low));
+            }
+            if idx > 0 {
+                words.push(name(idx, "on", false));
+            }
+            parts.push(words.join(" "));
+        }
+        return parts.join(" ");
+    }
+    for (idx, group) in groups.iter().enumerate().rev() {
+        let group = *group;
+        if group == 0 {
+            continue;
+        }
+        let noun = match (idx, scale) {
+            (0, _) | (1, _) => None,
+            (_, Scale::Short) => Some(name(idx - 1, "on", french)),
+            (_, Scale::Long) if idx % 2 == 0 => Some(name(idx / 2, "on", french)),
+            (_, Scale::Long) => Some(name(idx / 2, "ard", french)),
+        };
+        let part = match (idx, noun, french) {
+            (1, _, false) => chunk_en(group) + " thousand",
+            (1, _, true) if group == 1 => String::from("mille"),
+            (1, _, true) => chunk_fr(group, language, false) + " mille",
+            (_, Some(noun), false) => chunk_en(group) + " " + &noun,
+            (_, Some(noun), true) => {
+                let s = if group > 1 { "s" } else { "" };
+                chunk_fr(group, language, true) + " " + &noun + s
+            }
+            (_, None, false) => chunk_en(group),
+            (_, None, true) => chunk_fr(group, language, true),
+        };
+        parts.push(part);
+    }
+    parts.join(" ")
+}
+
+// read a number into limbs. Lenient reading accepts any case, "and", and any spelling made of the
+// words of the language, strict reading only accepts the numbers as they are written by format.
+fn parse(entry: &str, language: Language, scale: Scale, strict: bool) -> Option<Vec<u32>> {
+    let lower = entry.to_lowercase();
+    let words: Vec<&str> = lower
+        .split(|c: char| c.is_whitespace() || c == '-')
+        .filter(|word| !word.is_empty())
+        .collect();
+    if words.is_empty() {
+        return None;
+    }
+    let french = language.is_french();
+    let mut total: Vec<u32> = vec![];
+    if words != [language.zero()] {
+        let mut roots: HashMap<String, usize> = HashMap::new();
+        for rank in 0..1000 {
+            roots.insert(root(rank, french), rank);
+        }
+        // what is read below a large number name : below a million and below a thousand
+        let mut group: u64 = 0;
+        let mut small: u64 = 0;
+        let mut previous = "";
+        for word in words.iter() {
+            let word = *word;
+            let ones = if french { &ONES_FR[1..] } else { &ONES_EN[1..] };
+            let tens = if french { &TENS_FR[..] } else { &TENS_EN[..] };
+            if french && (word == "vingts" || word == "vingt") {
+                // quatre-vingts is four times twenty
+                small += if previous == "quatre" { 76 } else { 20 };
+            } else if let Some(idx) = ones.iter().position(|one| *one == word) {
+                small += idx as u64 + 1;
+            } else if let Some(idx) = tens.iter().position(|ten| *ten == word) {
+                small += 10 * idx as u64;
+            } else if word == "hundred" || (french && (word == "cent" || word == "cents")) {
+                small = small.max(1) * 100;
+            } else if word == "thousand" || (french && word == "mille") {
+                group += small.max(1) * 1000;
+                small = 0;
+            } else if word == "and" || (french && word == "et") {
+            } else {
+                let (rank, ard) = read_name(word, &roots)?;
+                let exponent = exponent(language, scale, rank, ard)?;
+                if exponent > MAX_EXPONENT {
+                    return None;
+                }
+                let mut value = power_of_ten(exponent);
+                let count = (group + small).max(1);
+                limbs_mul_small(&mut value, count as u32);
+                limbs_add(&mut total, &value);
+                group = 0;
+                small = 0;
+            }
+            if group + small >= 1_000_000 {
+                return None;
+            }
+            previous = word;
+        }
+        let mut rest = vec![(group + small) as u32];
+        limbs_trim(&mut rest);
+        limbs_add(&mut total, &rest);
+        limbs_trim(&mut total);
+    }
+    if strict && format(&total, language, scale) != entry {
+        return None;
+    }
+    Some(total)
+}